@@ -2,6 +2,12 @@
 fn main() {
     // Este script só precisa ser executado se o arquivo de recurso mudar.
     println!("cargo:rerun-if-changed=assets/app.rc");
+    // O recurso (ícone do executável) só existe no Windows. O alvo é verificado pela
+    // variável do Cargo, e não por '#[cfg]', porque o script roda na máquina de
+    // compilação, que pode ser diferente do alvo em compilações cruzadas.
+    if std::env::var("CARGO_CFG_TARGET_OS").as_deref() != Ok("windows") {
+        return;
+    }
     // Usa a crate embed-resource para compilar e embutir o recurso.
     embed_resource::compile("assets/app.rc", embed_resource::NONE);
 }
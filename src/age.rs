@@ -0,0 +1,370 @@
+// Cálculo das idades cronológica e corrigida, separado da interface gráfica para
+// poder ser testado sem abrir uma janela.
+use chrono::{Datelike, Months, NaiveDate};
+
+/// Duração média de um mês em dias (365,25 ÷ 12), usada nos meses totais quando
+/// essa contagem é escolhida nas configurações (veja `MonthCount`).
+pub const AVERAGE_MONTH_DAYS: f64 = 30.4375;
+
+/// Idade gestacional do termo, em semanas: a referência da data provável do parto e,
+/// por padrão, da idade corrigida.
+pub const FULL_TERM_WEEKS: i32 = 40;
+
+/// Armazena a idade cronológica calculada.
+pub struct ChronologicalAge {
+    pub years: i32,
+    pub months: i32,
+    pub days: i32,
+    pub total_weeks: i64,
+    /// Dias além das semanas completas (0 a 6), como na idade corrigida.
+    pub days_in_week: i64,
+    /// Meses completos de calendário, sempre iguais a `years * 12 + months`.
+    pub total_months: i64,
+}
+
+/// Armazena a idade corrigida calculada.
+pub struct CorrectedAge {
+    pub years: i32,
+    pub months: i32,
+    pub days: i32,
+    pub weeks: i64,
+    pub days_in_week: i64,
+    pub total_months: i64,
+}
+
+/// Calcula a idade cronológica com base na data de nascimento e na data atual.
+pub fn calculate_chronological_age(birthdate: NaiveDate, today: NaiveDate) -> ChronologicalAge {
+    // Meses completos: o último "mesversário" que não passa de hoje. Em meses mais
+    // curtos que o dia do nascimento, o chrono usa o último dia do mês (nascido em
+    // 30/03, completa mais um mês em 28/02), e os dias são contados a partir daí.
+    let mut completed_months =
+        (today.year() - birthdate.year()) * 12 + today.month() as i32 - birthdate.month() as i32;
+    let month_anniversary = |months: i32| {
+        birthdate
+            .checked_add_months(Months::new(months.max(0) as u32))
+            .unwrap_or(birthdate)
+    };
+    if completed_months > 0 && month_anniversary(completed_months) > today {
+        completed_months -= 1;
+    }
+    let completed_months = completed_months.max(0);
+    let years = completed_months / 12;
+    let months = completed_months % 12;
+    let days = today
+        .signed_duration_since(month_anniversary(completed_months))
+        .num_days() as i32;
+
+    // Calcula o total de dias e semanas. Os meses totais vêm da mesma decomposição
+    // em anos, meses e dias, para que as duas formas nunca discordem.
+    let total_days = today.signed_duration_since(birthdate).num_days();
+    let total_weeks = total_days / 7;
+    let days_in_week = total_days % 7;
+    let total_months = completed_months as i64;
+
+    ChronologicalAge {
+        years,
+        months,
+        days,
+        total_weeks,
+        days_in_week,
+        total_months,
+    }
+}
+
+/// Calcula a data de nascimento corrigida, isto é, a data em que o bebê completaria
+/// `term_weeks` semanas de gestação (em geral 40). Para bebês nascidos depois disso,
+/// é a própria data de nascimento.
+pub fn calculate_corrected_birthdate(
+    birthdate: NaiveDate,
+    gestational_weeks: i32,
+    gestational_days: i32,
+    term_weeks: i32,
+) -> NaiveDate {
+    birthdate
+        + chrono::Duration::days(calculate_prematurity_days(
+            gestational_weeks,
+            gestational_days,
+            term_weeks,
+        ))
+}
+
+/// Calcula a data provável do parto (DPP): a data em que a gestação completaria 40
+/// semanas. Ao contrário de `calculate_corrected_birthdate`, não é limitada ao
+/// nascimento: para bebês nascidos após 40 semanas, fica antes da data de nascimento.
+pub fn calculate_due_date(
+    birthdate: NaiveDate,
+    gestational_weeks: i32,
+    gestational_days: i32,
+) -> NaiveDate {
+    let total_gestational_days = (gestational_weeks * 7 + gestational_days) as i64;
+    birthdate + chrono::Duration::days((FULL_TERM_WEEKS * 7) as i64 - total_gestational_days)
+}
+
+/// Calcula quantos dias faltaram para a gestação completar `term_weeks` semanas.
+/// Para bebês nascidos depois disso, retorna 0.
+pub fn calculate_prematurity_days(
+    gestational_weeks: i32,
+    gestational_days: i32,
+    term_weeks: i32,
+) -> i64 {
+    let total_gestational_days = gestational_weeks * 7 + gestational_days;
+    (term_weeks * 7 - total_gestational_days).max(0) as i64
+}
+
+/// Calcula a idade pós-menstrual em dias: a idade gestacional ao nascer somada aos
+/// dias desde o nascimento.
+pub fn calculate_post_menstrual_days(
+    birthdate: NaiveDate,
+    today: NaiveDate,
+    gestational_weeks: i32,
+    gestational_days: i32,
+) -> i64 {
+    let total_gestational_days = (gestational_weeks * 7 + gestational_days) as i64;
+    total_gestational_days + today.signed_duration_since(birthdate).num_days()
+}
+
+/// Data em que o bebê atinge a idade corrigida de `months` meses completos.
+pub fn date_at_corrected_months(corrected_birthdate: NaiveDate, months: u32) -> NaiveDate {
+    corrected_birthdate + Months::new(months)
+}
+
+/// Calcula a idade corrigida, ajustando para a prematuridade em relação a um termo
+/// de `term_weeks` semanas (`FULL_TERM_WEEKS`, salvo em protocolos que corrigem
+/// para 37 ou 38 semanas).
+pub fn calculate_corrected_age(
+    birthdate: NaiveDate,
+    today: NaiveDate,
+    gestational_weeks: i32,
+    gestational_days: i32,
+    term_weeks: i32,
+) -> CorrectedAge {
+    let prematurity_days =
+        calculate_prematurity_days(gestational_weeks, gestational_days, term_weeks);
+
+    // Se não for prematuro, a idade corrigida é a mesma que a cronológica.
+    if prematurity_days == 0 {
+        let chronological = calculate_chronological_age(birthdate, today);
+        return CorrectedAge {
+            years: chronological.years,
+            months: chronological.months,
+            days: chronological.days,
+            weeks: chronological.total_weeks,
+            days_in_week: chronological.days_in_week,
+            total_months: chronological.total_months,
+        };
+    }
+
+    // Calcula a data de nascimento corrigida.
+    let corrected_birthdate =
+        calculate_corrected_birthdate(birthdate, gestational_weeks, gestational_days, term_weeks);
+    // Antes da data do termo, a idade corrigida é zero (e não negativa).
+    let corrected_age_as_chrono =
+        calculate_chronological_age(corrected_birthdate, today.max(corrected_birthdate));
+    let corrected_total_days = today
+        .signed_duration_since(corrected_birthdate)
+        .num_days()
+        .max(0);
+    let corrected_weeks = corrected_total_days / 7;
+    let corrected_days_in_week = corrected_total_days % 7;
+
+    CorrectedAge {
+        years: corrected_age_as_chrono.years,
+        months: corrected_age_as_chrono.months,
+        days: corrected_age_as_chrono.days,
+        weeks: corrected_weeks,
+        days_in_week: corrected_days_in_week,
+        total_months: corrected_age_as_chrono.total_months,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dates::date;
+
+    #[test]
+    fn same_day_birth_is_zero() {
+        let age = calculate_chronological_age(date(10, 6, 2024), date(10, 6, 2024));
+        assert_eq!((age.years, age.months, age.days), (0, 0, 0));
+        assert_eq!((age.total_weeks, age.total_months), (0, 0));
+    }
+
+    #[test]
+    fn leap_day_birth_completes_a_year_on_february_28() {
+        let age = calculate_chronological_age(date(29, 2, 2024), date(28, 2, 2025));
+        assert_eq!((age.years, age.months, age.days), (1, 0, 0));
+        let age = calculate_chronological_age(date(29, 2, 2024), date(27, 2, 2025));
+        assert_eq!((age.years, age.months, age.days), (0, 11, 29));
+    }
+
+    #[test]
+    fn february_29_is_counted_in_leap_years() {
+        // De 01/02 a 01/03 são 29 dias em 2024 e 28 em 2023.
+        let age = calculate_chronological_age(date(1, 2, 2024), date(1, 3, 2024));
+        assert_eq!((age.months, age.days, age.total_weeks), (1, 0, 4));
+        let age = calculate_chronological_age(date(1, 2, 2024), date(29, 2, 2024));
+        assert_eq!((age.months, age.days), (0, 28));
+        let age = calculate_chronological_age(date(1, 2, 2023), date(28, 2, 2023));
+        assert_eq!((age.months, age.days), (0, 27));
+    }
+
+    #[test]
+    fn days_borrowed_across_non_leap_february() {
+        // 31/01 não existe em fevereiro: o mês se completa em 28/02.
+        let age = calculate_chronological_age(date(31, 1, 2023), date(1, 3, 2023));
+        assert_eq!((age.years, age.months, age.days), (0, 1, 1));
+        // De 15/02 a 10/03 são 13 dias de fevereiro mais 10 de março.
+        let age = calculate_chronological_age(date(15, 1, 2023), date(10, 3, 2023));
+        assert_eq!((age.years, age.months, age.days), (0, 1, 23));
+    }
+
+    #[test]
+    fn days_borrowed_across_leap_february() {
+        let age = calculate_chronological_age(date(31, 1, 2024), date(1, 3, 2024));
+        assert_eq!((age.years, age.months, age.days), (0, 1, 1));
+        let age = calculate_chronological_age(date(15, 1, 2024), date(10, 3, 2024));
+        assert_eq!((age.years, age.months, age.days), (0, 1, 24));
+    }
+
+    #[test]
+    fn months_borrowed_across_the_year() {
+        let age = calculate_chronological_age(date(20, 12, 2022), date(5, 1, 2024));
+        assert_eq!((age.years, age.months, age.days), (1, 0, 16));
+        let age = calculate_chronological_age(date(20, 12, 2022), date(19, 12, 2023));
+        assert_eq!((age.years, age.months, age.days), (0, 11, 29));
+    }
+
+    #[test]
+    fn full_term_and_post_term_have_no_correction() {
+        assert_eq!(calculate_prematurity_days(40, 0, FULL_TERM_WEEKS), 0);
+        assert_eq!(calculate_prematurity_days(42, 3, FULL_TERM_WEEKS), 0);
+        assert_eq!(
+            calculate_corrected_birthdate(date(1, 1, 2024), 41, 2, FULL_TERM_WEEKS),
+            date(1, 1, 2024)
+        );
+        let corrected =
+            calculate_corrected_age(date(1, 1, 2024), date(15, 1, 2024), 41, 2, FULL_TERM_WEEKS);
+        assert_eq!((corrected.weeks, corrected.days_in_week), (2, 0));
+    }
+
+    #[test]
+    fn chronological_age_keeps_the_days_beyond_whole_weeks() {
+        // De 01/01 a 15/05/2024 são 135 dias: 19 semanas e 2 dias.
+        let age = calculate_chronological_age(date(1, 1, 2024), date(15, 5, 2024));
+        assert_eq!((age.total_weeks, age.days_in_week), (19, 2));
+        // A termo, a idade corrigida tem o mesmo resto.
+        let corrected =
+            calculate_corrected_age(date(1, 1, 2024), date(15, 5, 2024), 40, 0, FULL_TERM_WEEKS);
+        assert_eq!((corrected.weeks, corrected.days_in_week), (19, 2));
+    }
+
+    #[test]
+    fn total_months_match_the_years_and_months() {
+        // Inclui nascimentos no fim do mês, em que 30,4375 dias por mês dariam um mês
+        // a menos do que a decomposição em anos, meses e dias.
+        let births = [date(31, 1, 2024), date(29, 2, 2024), date(15, 6, 2023)];
+        for birthdate in births {
+            for days in (0..800).step_by(7) {
+                let today = birthdate + chrono::Duration::days(days);
+                let age = calculate_chronological_age(birthdate, today);
+                assert_eq!(age.total_months, (age.years * 12 + age.months) as i64);
+                let corrected = calculate_corrected_age(birthdate, today, 30, 3, FULL_TERM_WEEKS);
+                assert_eq!(
+                    corrected.total_months,
+                    (corrected.years * 12 + corrected.months) as i64
+                );
+            }
+        }
+        let age = calculate_chronological_age(date(31, 1, 2024), date(30, 4, 2024));
+        assert_eq!((age.months, age.total_months), (3, 3));
+    }
+
+    #[test]
+    fn a_37_week_term_shortens_the_correction() {
+        // Nascido com 32 semanas, 60 dias de vida: 8 semanas de correção para o
+        // termo de 40 semanas e 5 semanas para o de 37.
+        let to_40 = calculate_corrected_age(date(1, 1, 2024), date(1, 3, 2024), 32, 0, 40);
+        assert_eq!((to_40.weeks, to_40.days_in_week), (0, 4));
+        let to_37 = calculate_corrected_age(date(1, 1, 2024), date(1, 3, 2024), 32, 0, 37);
+        assert_eq!((to_37.weeks, to_37.days_in_week), (3, 4));
+        assert_eq!(calculate_prematurity_days(32, 0, 37), 35);
+        assert_eq!(
+            calculate_corrected_birthdate(date(1, 1, 2024), 32, 0, 37),
+            date(5, 2, 2024)
+        );
+        // Quem nasceu com 38 semanas já passou do termo de 37: não há correção.
+        assert_eq!(calculate_prematurity_days(38, 0, 37), 0);
+    }
+
+    #[test]
+    fn due_date_of_a_32_week_birth_is_8_weeks_later() {
+        assert_eq!(
+            calculate_due_date(date(1, 1, 2024), 32, 0),
+            date(26, 2, 2024)
+        );
+        assert_eq!(
+            calculate_due_date(date(1, 1, 2024), 32, 4),
+            date(22, 2, 2024)
+        );
+        // Para prematuros, a DPP coincide com a data do termo usada na correção.
+        assert_eq!(
+            calculate_due_date(date(1, 1, 2024), 32, 4),
+            calculate_corrected_birthdate(date(1, 1, 2024), 32, 4, FULL_TERM_WEEKS)
+        );
+    }
+
+    #[test]
+    fn due_date_of_a_post_term_birth_is_before_birth() {
+        assert_eq!(
+            calculate_due_date(date(10, 1, 2024), 41, 2),
+            date(1, 1, 2024)
+        );
+        assert_eq!(
+            calculate_due_date(date(10, 1, 2024), 40, 0),
+            date(10, 1, 2024)
+        );
+    }
+
+    #[test]
+    fn post_menstrual_age_adds_days_since_birth() {
+        // 32s4d ao nascer e 10 dias de vida: 34 semanas e 0 dias.
+        assert_eq!(
+            calculate_post_menstrual_days(date(1, 1, 2024), date(11, 1, 2024), 32, 4),
+            34 * 7
+        );
+        assert_eq!(
+            calculate_post_menstrual_days(date(1, 1, 2024), date(1, 1, 2024), 40, 2),
+            282
+        );
+    }
+
+    #[test]
+    fn one_day_short_of_term_is_one_day_of_prematurity() {
+        assert_eq!(calculate_prematurity_days(39, 6, FULL_TERM_WEEKS), 1);
+        assert_eq!(
+            calculate_corrected_birthdate(date(31, 12, 2023), 39, 6, FULL_TERM_WEEKS),
+            date(1, 1, 2024)
+        );
+    }
+
+    #[test]
+    fn corrected_age_is_zero_before_term() {
+        // 28 semanas: o termo é 12 semanas depois do nascimento.
+        let corrected =
+            calculate_corrected_age(date(1, 1, 2024), date(1, 2, 2024), 28, 0, FULL_TERM_WEEKS);
+        assert_eq!((corrected.weeks, corrected.days_in_week), (0, 0));
+        assert_eq!(
+            (corrected.years, corrected.months, corrected.days),
+            (0, 0, 0)
+        );
+        assert_eq!(corrected.total_months, 0);
+    }
+
+    #[test]
+    fn corrected_age_on_the_term_date_is_zero() {
+        let term = calculate_corrected_birthdate(date(1, 1, 2024), 32, 0, FULL_TERM_WEEKS);
+        assert_eq!(term, date(26, 2, 2024));
+        let corrected = calculate_corrected_age(date(1, 1, 2024), term, 32, 0, FULL_TERM_WEEKS);
+        assert_eq!((corrected.weeks, corrected.days_in_week), (0, 0));
+    }
+}
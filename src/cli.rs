@@ -0,0 +1,188 @@
+// Modo de linha de comando, para calcular em scripts sem abrir a janela:
+//
+//     calculadora_gestacional --birth 12/05/2023 --weeks 30 --days 4 [--today 01/01/2024]
+//
+// Os argumentos são lidos pelo 'clap', que também gera a ajuda ('--help') e as
+// mensagens de argumentos inválidos. Os valores passam pelas mesmas validações dos
+// campos do formulário, e o resultado é o mesmo texto exibido na interface. Sem
+// argumentos (ou apenas com '--verbose', tratado em 'diagnostics.rs'), o programa
+// abre a janela normalmente.
+use clap::{ArgAction, Parser};
+use std::ffi::OsString;
+
+/// Argumentos aceitos pelo programa.
+#[derive(Parser, Debug)]
+#[command(
+    name = "calculadora_gestacional",
+    about = "Calcula a idade cronológica e a idade corrigida de um bebê.",
+    after_help = "Sem argumentos, abre a janela da calculadora.",
+    disable_help_flag = true
+)]
+struct Cli {
+    /// Data de nascimento
+    #[arg(long, value_name = "DD/MM/AAAA", requires = "weeks")]
+    birth: Option<String>,
+    /// Semanas de gestação ao nascer (aceita também "32+4" ou "31-33")
+    #[arg(
+        long,
+        value_name = "SEMANAS",
+        requires = "birth",
+        allow_hyphen_values = true
+    )]
+    weeks: Option<String>,
+    /// Dias além das semanas completas, de 0 a 6 (padrão: 0)
+    #[arg(
+        long,
+        value_name = "DIAS",
+        requires = "birth",
+        allow_hyphen_values = true
+    )]
+    days: Option<String>,
+    /// Data de referência do cálculo (padrão: hoje)
+    #[arg(long, value_name = "DD/MM/AAAA", requires = "birth")]
+    today: Option<String>,
+    /// Descreve no 'stderr' como as entradas foram interpretadas
+    #[arg(long)]
+    verbose: bool,
+    /// Mostra esta ajuda
+    #[arg(short, long, action = ArgAction::Help)]
+    help: Option<bool>,
+}
+
+/// Valores informados na linha de comando, ainda como texto, como nos campos do
+/// formulário.
+#[derive(Debug, PartialEq)]
+pub struct Args {
+    pub birth: String,
+    pub weeks: String,
+    pub days: String,
+    pub today: Option<String>,
+}
+
+/// O que o programa deve fazer, conforme os argumentos.
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    /// Abrir a janela da calculadora.
+    Gui,
+    /// Calcular e imprimir o resultado.
+    Calculate(Args),
+}
+
+/// Interpreta os argumentos do programa (incluindo o nome do executável). Aceita
+/// tanto "--birth 12/05/2023" quanto "--birth=12/05/2023". Com '--help' ou com
+/// argumentos inválidos, retorna o erro do 'clap', que já traz o texto a exibir
+/// (`clap::Error::exit`).
+pub fn parse<I, T>(args: I) -> Result<Command, clap::Error>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    let cli = Cli::try_parse_from(args)?;
+    Ok(match (cli.birth, cli.weeks) {
+        (Some(birth), Some(weeks)) => Command::Calculate(Args {
+            birth,
+            weeks,
+            days: cli.days.unwrap_or_else(|| "0".to_string()),
+            today: cli.today,
+        }),
+        _ => Command::Gui,
+    })
+}
+
+/// No Windows, a versão de lançamento usa o subsistema gráfico, para não abrir uma
+/// janela de console junto com a calculadora, e por isso não escreve no terminal de
+/// onde foi chamada. Com argumentos, o programa se conecta ao console do processo
+/// pai ('cmd' ou PowerShell) para que o resultado, os avisos e a ajuda apareçam nele.
+/// Iniciado por um atalho, não há console para conectar, e a falha é ignorada.
+#[cfg(all(windows, not(debug_assertions)))]
+pub fn attach_parent_console() {
+    /// Pede o console do processo pai em vez do de um processo específico.
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+
+    // SAFETY: 'AttachConsole' não recebe ponteiros e só falha (retornando 0) quando
+    // não há console para conectar.
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> Result<Command, clap::Error> {
+        parse(std::iter::once("calculadora_gestacional").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn no_arguments_open_the_window() {
+        assert_eq!(parse_args(&[]).unwrap(), Command::Gui);
+        assert_eq!(parse_args(&["--verbose"]).unwrap(), Command::Gui);
+    }
+
+    #[test]
+    fn calculation_arguments_are_read_in_both_forms() {
+        assert_eq!(
+            parse_args(&[
+                "--birth",
+                "12/05/2023",
+                "--weeks=30",
+                "--days",
+                "4",
+                "--today=01/01/2024"
+            ])
+            .unwrap(),
+            Command::Calculate(Args {
+                birth: "12/05/2023".to_string(),
+                weeks: "30".to_string(),
+                days: "4".to_string(),
+                today: Some("01/01/2024".to_string()),
+            })
+        );
+        assert_eq!(
+            parse_args(&["--birth", "12/05/2023", "--weeks", "32+4"]).unwrap(),
+            Command::Calculate(Args {
+                birth: "12/05/2023".to_string(),
+                weeks: "32+4".to_string(),
+                days: "0".to_string(),
+                today: None,
+            })
+        );
+    }
+
+    #[test]
+    fn negative_values_reach_the_form_validation() {
+        // "-5" é um valor (rejeitado depois, como no formulário), e não uma opção.
+        assert_eq!(
+            parse_args(&["--birth", "12/05/2023", "--weeks", "-5"]).unwrap(),
+            Command::Calculate(Args {
+                birth: "12/05/2023".to_string(),
+                weeks: "-5".to_string(),
+                days: "0".to_string(),
+                today: None,
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_arguments_are_reported() {
+        assert!(parse_args(&["--birth", "12/05/2023"]).is_err());
+        assert!(parse_args(&["--days", "3"]).is_err());
+        assert!(parse_args(&["--weeks", "30", "--birth"]).is_err());
+        assert!(parse_args(&["--idade", "3"]).is_err());
+    }
+
+    #[test]
+    fn help_is_shown_in_portuguese() {
+        let error = parse_args(&["--help"]).unwrap_err();
+        assert_eq!(error.kind(), clap::error::ErrorKind::DisplayHelp);
+        let help = error.to_string();
+        assert!(help.contains("--birth <DD/MM/AAAA>"));
+        assert!(help.contains("Sem argumentos, abre a janela da calculadora."));
+    }
+}
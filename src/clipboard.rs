@@ -0,0 +1,238 @@
+// Cópia para a área de transferência e mensagens amigáveis para as falhas,
+// específicas para cada formato copiado.
+//
+// Observação sobre plataformas: a 'arboard' grava o texto em Unicode (UTF-16 no
+// Windows, UTF-8 no X11/Wayland e no macOS), mas alguns gerenciadores de área de
+// transferência e sessões remotas (RDP, VNC, terminais antigos) convertem o texto
+// para uma página de código de 8 bits e trocam acentos e emojis por '?'. Por isso o
+// texto copiado é conferido e, se voltar alterado, é copiado de novo sem acentos.
+
+use crate::i18n::{t, tf, Lang};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use arboard::{Clipboard, Error};
+
+#[cfg(target_arch = "wasm32")]
+pub use web::{Clipboard, Error};
+
+/// No navegador, a área de transferência só pode ser usada de forma assíncrona e
+/// com permissão do usuário. `Clipboard::new` sempre falha e os botões de copiar
+/// ficam desativados, com a mesma explicação de quando a área de transferência do
+/// sistema não está disponível.
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use std::borrow::Cow;
+
+    #[allow(dead_code)]
+    pub enum Error {
+        ClipboardNotSupported,
+        ClipboardOccupied,
+        ConversionFailure,
+        Unknown { description: String },
+    }
+
+    pub struct Clipboard;
+
+    impl Clipboard {
+        pub fn new() -> Result<Self, Error> {
+            Err(Error::ClipboardNotSupported)
+        }
+
+        pub fn set_text<'a>(&mut self, _text: impl Into<Cow<'a, str>>) -> Result<(), Error> {
+            Err(Error::ClipboardNotSupported)
+        }
+
+        pub fn get_text(&mut self) -> Result<String, Error> {
+            Err(Error::ClipboardNotSupported)
+        }
+
+        pub fn set_html<'a>(
+            &mut self,
+            _html: impl Into<Cow<'a, str>>,
+            _alt_text: Option<impl Into<Cow<'a, str>>>,
+        ) -> Result<(), Error> {
+            Err(Error::ClipboardNotSupported)
+        }
+    }
+}
+
+/// Formato em que o resultado está sendo copiado.
+#[derive(Clone, Copy)]
+pub enum ClipboardOp {
+    Text,
+    Html,
+}
+
+impl ClipboardOp {
+    /// Nome do formato para compor as mensagens.
+    fn name(self) -> &'static str {
+        match self {
+            ClipboardOp::Text => "texto",
+            ClipboardOp::Html => "HTML",
+        }
+    }
+}
+
+/// Converte um erro da 'arboard' em uma mensagem para o usuário, indicando em
+/// qual formato a cópia falhou.
+#[cfg_attr(target_arch = "wasm32", allow(unreachable_patterns))]
+pub fn clipboard_error_message(op: ClipboardOp, error: &Error, lang: Lang) -> String {
+    let name = t(op.name(), lang);
+    match error {
+        Error::ClipboardNotSupported => tf(
+            "Falha ao copiar como {}: formato não suportado pela área de transferência deste sistema.",
+            lang,
+            &[&name],
+        ),
+        Error::ClipboardOccupied => tf(
+            "Falha ao copiar como {}: a área de transferência está em uso por outro programa. Tente novamente.",
+            lang,
+            &[&name],
+        ),
+        Error::ConversionFailure => tf(
+            "Falha ao copiar como {}: não foi possível converter o resultado.",
+            lang,
+            &[&name],
+        ),
+        Error::Unknown { description } => tf(
+            "Falha ao copiar como {}: erro inesperado ({}).",
+            lang,
+            &[&name, description],
+        ),
+        _ => tf(
+            "Falha ao copiar como {}: a área de transferência não respondeu.",
+            lang,
+            &[&name],
+        ),
+    }
+}
+
+/// Copia um texto e confere se ele foi gravado sem alterações. Se a área de
+/// transferência tiver corrompido os caracteres não ASCII, copia de novo a versão
+/// sem acentos (`to_ascii`) e retorna `Ok(true)`.
+pub fn set_text_checked(clipboard: &mut Clipboard, text: &str) -> Result<bool, Error> {
+    clipboard.set_text(text)?;
+    if text.is_ascii() {
+        return Ok(false);
+    }
+    // Se não for possível ler de volta, não há como conferir: mantém a cópia.
+    match clipboard.get_text() {
+        Ok(copied) if copied.replace("\r\n", "\n") != text.replace("\r\n", "\n") => {
+            clipboard.set_text(to_ascii(text))?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Remove os acentos dos caracteres usados em português e descarta os demais
+/// caracteres não ASCII (como emojis), para áreas de transferência sem Unicode.
+pub fn to_ascii(text: &str) -> String {
+    text.chars()
+        .filter_map(|c| {
+            let replacement = match c {
+                'á' | 'à' | 'â' | 'ã' | 'ä' | 'ª' => 'a',
+                'Á' | 'À' | 'Â' | 'Ã' | 'Ä' => 'A',
+                'é' | 'è' | 'ê' | 'ë' => 'e',
+                'É' | 'È' | 'Ê' | 'Ë' => 'E',
+                'í' | 'ì' | 'î' | 'ï' => 'i',
+                'Í' | 'Ì' | 'Î' | 'Ï' => 'I',
+                'ó' | 'ò' | 'ô' | 'õ' | 'ö' | 'º' => 'o',
+                'Ó' | 'Ò' | 'Ô' | 'Õ' | 'Ö' => 'O',
+                'ú' | 'ù' | 'û' | 'ü' => 'u',
+                'Ú' | 'Ù' | 'Û' | 'Ü' => 'U',
+                'ç' => 'c',
+                'Ç' => 'C',
+                '–' | '—' | '−' => '-',
+                '÷' => '/',
+                '×' => 'x',
+                c if c.is_ascii() => c,
+                _ => return None,
+            };
+            Some(replacement)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accents_are_removed() {
+        assert_eq!(
+            to_ascii("Idade Pós-Menstrual: 33 semanas – 5º dia; concepção"),
+            "Idade Pos-Menstrual: 33 semanas - 5o dia; concepcao"
+        );
+    }
+
+    #[test]
+    fn emoji_and_other_symbols_are_dropped() {
+        assert_eq!(to_ascii("👶 Bebê 📋"), " Bebe ");
+    }
+
+    #[test]
+    fn ascii_text_is_unchanged() {
+        let text = "Idade Corrigida: 11 semanas (2 meses) e 2 dias\n";
+        assert_eq!(to_ascii(text), text);
+    }
+
+    #[test]
+    fn each_error_has_its_own_message_for_each_format() {
+        let unknown = || Error::Unknown {
+            description: "X11 fechado".to_string(),
+        };
+        for (op, name) in [(ClipboardOp::Text, "texto"), (ClipboardOp::Html, "HTML")] {
+            let message = |error: &Error| clipboard_error_message(op, error, Lang::PtBr);
+            assert_eq!(
+                message(&Error::ClipboardNotSupported),
+                format!(
+                    "Falha ao copiar como {}: formato não suportado pela área de \
+                     transferência deste sistema.",
+                    name
+                )
+            );
+            assert_eq!(
+                message(&Error::ClipboardOccupied),
+                format!(
+                    "Falha ao copiar como {}: a área de transferência está em uso por \
+                     outro programa. Tente novamente.",
+                    name
+                )
+            );
+            assert_eq!(
+                message(&Error::ConversionFailure),
+                format!(
+                    "Falha ao copiar como {}: não foi possível converter o resultado.",
+                    name
+                )
+            );
+            assert_eq!(
+                message(&unknown()),
+                format!(
+                    "Falha ao copiar como {}: erro inesperado (X11 fechado).",
+                    name
+                )
+            );
+            assert_eq!(
+                message(&Error::ContentNotAvailable),
+                format!(
+                    "Falha ao copiar como {}: a área de transferência não respondeu.",
+                    name
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn error_messages_are_localized() {
+        assert_eq!(
+            clipboard_error_message(ClipboardOp::Text, &Error::ClipboardOccupied, Lang::En),
+            "Failed to copy as text: the clipboard is in use by another program. Try again."
+        );
+        assert_eq!(
+            clipboard_error_message(ClipboardOp::Html, &Error::ConversionFailure, Lang::En),
+            "Failed to copy as HTML: the result could not be converted."
+        );
+    }
+}
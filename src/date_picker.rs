@@ -0,0 +1,189 @@
+// Calendário para escolher uma data com o mouse, ao lado de um campo de texto.
+// O campo continua editável: o calendário apenas escreve a data escolhida nele.
+use crate::dates;
+use crate::i18n::{t, tf, Lang};
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use eframe::egui;
+
+/// Nomes dos meses exibidos no cabeçalho do calendário.
+const MONTH_NAMES: [&str; 12] = [
+    "Janeiro",
+    "Fevereiro",
+    "Março",
+    "Abril",
+    "Maio",
+    "Junho",
+    "Julho",
+    "Agosto",
+    "Setembro",
+    "Outubro",
+    "Novembro",
+    "Dezembro",
+];
+
+/// Iniciais dos dias da semana, começando no domingo como nos calendários brasileiros.
+/// As iniciais se repetem ("S" de segunda, sexta e sábado), então não servem de
+/// chave para `t` e ficam aqui para cada idioma.
+fn weekday_initials(lang: Lang) -> [&'static str; 7] {
+    match lang {
+        Lang::PtBr => ["D", "S", "T", "Q", "Q", "S", "S"],
+        Lang::En => ["S", "M", "T", "W", "T", "F", "S"],
+        Lang::Es => ["D", "L", "M", "X", "J", "V", "S"],
+    }
+}
+
+/// Estado do calendário guardado na memória do egui entre os quadros: o primeiro
+/// dia do mês exibido, ou `None` quando o calendário está fechado.
+#[derive(Clone, Copy, Default)]
+struct PickerState {
+    shown_month: Option<NaiveDate>,
+}
+
+/// Botão que abre um calendário abaixo dele. Ao escolher um dia, escreve a data em
+/// `text` no formato DD/MM/AAAA e fecha o calendário. O calendário abre no mês da
+/// data já digitada, se for válida, ou no mês de `today`. Retorna verdadeiro
+/// quando uma data é escolhida.
+pub fn date_picker_button(
+    ui: &mut egui::Ui,
+    id_source: &str,
+    text: &mut String,
+    today: NaiveDate,
+    lang: Lang,
+) -> bool {
+    let id = ui.make_persistent_id(id_source);
+    let mut state: PickerState = ui.data(|d| d.get_temp(id)).unwrap_or_default();
+    let typed = dates::parse_date(text, today);
+
+    let button = ui
+        .button("📅")
+        .on_hover_text(t("Escolher no calendário", lang));
+    if button.clicked() {
+        state.shown_month = match state.shown_month {
+            Some(_) => None,
+            None => Some(first_of_month(typed.unwrap_or(today))),
+        };
+    }
+
+    let mut chosen = None;
+    if let Some(month) = state.shown_month {
+        let area = egui::Area::new(id.with("calendar"))
+            .order(egui::Order::Foreground)
+            .constrain(true)
+            .fixed_pos(button.rect.left_bottom())
+            .show(ui.ctx(), |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        if ui.small_button("◀").clicked() {
+                            state.shown_month = Some(month - Months::new(1));
+                        }
+                        ui.label(tf(
+                            "{} de {}",
+                            lang,
+                            &[
+                                &t(MONTH_NAMES[month.month0() as usize], lang),
+                                &month.year(),
+                            ],
+                        ));
+                        if ui.small_button("▶").clicked() {
+                            state.shown_month = Some(month + Months::new(1));
+                        }
+                    });
+                    egui::Grid::new(id.with("days")).show(ui, |ui| {
+                        for initial in weekday_initials(lang) {
+                            ui.label(egui::RichText::new(initial).weak());
+                        }
+                        ui.end_row();
+                        for week in month_grid(month) {
+                            for day in week {
+                                match day {
+                                    Some(date) => {
+                                        let mut label = egui::RichText::new(date.day().to_string());
+                                        if date == today {
+                                            label = label.strong();
+                                        }
+                                        if ui.selectable_label(typed == Some(date), label).clicked()
+                                        {
+                                            chosen = Some(date);
+                                        }
+                                    }
+                                    None => {
+                                        ui.label("");
+                                    }
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+                    if ui.small_button(t("Hoje", lang)).clicked() {
+                        chosen = Some(today);
+                    }
+                });
+            });
+
+        // Fecha ao escolher, com 'Esc' ou com um clique fora do calendário.
+        let closed = ui.input(|i| i.key_pressed(egui::Key::Escape))
+            || (area.response.clicked_elsewhere() && !button.clicked());
+        if chosen.is_some() || closed {
+            state.shown_month = None;
+        }
+    }
+    ui.data_mut(|d| d.insert_temp(id, state));
+
+    if let Some(date) = chosen {
+        *text = date.format(dates::DATE_FORMAT).to_string();
+    }
+    chosen.is_some()
+}
+
+/// Primeiro dia do mês da data.
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+/// Semanas do mês para o calendário, de domingo a sábado. Os dias de outros meses
+/// ficam vazios (`None`).
+fn month_grid(month: NaiveDate) -> Vec<[Option<NaiveDate>; 7]> {
+    let first = first_of_month(month);
+    let offset = first.weekday().num_days_from_sunday() as i64;
+    let mut weeks = Vec::new();
+    let mut start = first - Duration::days(offset);
+    while start.month() == first.month() || start < first {
+        let mut week = [None; 7];
+        for (i, slot) in week.iter_mut().enumerate() {
+            let date = start + Duration::days(i as i64);
+            if date.month() == first.month() {
+                *slot = Some(date);
+            }
+        }
+        weeks.push(week);
+        start += Duration::days(7);
+    }
+    weeks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dates::date;
+
+    #[test]
+    fn month_grid_starts_on_sunday() {
+        // 01/10/2026 é uma quinta-feira.
+        let weeks = month_grid(date(15, 10, 2026));
+        assert_eq!(weeks.len(), 5);
+        assert_eq!(weeks[0][..4], [None; 4]);
+        assert_eq!(weeks[0][4], Some(date(1, 10, 2026)));
+        assert_eq!(weeks[4][6], Some(date(31, 10, 2026)));
+    }
+
+    #[test]
+    fn month_grid_holds_every_day_once() {
+        for (month, days) in [(2, 28), (2, 29), (8, 31), (11, 30)] {
+            let year = if days == 29 { 2024 } else { 2026 };
+            let grid = month_grid(date(1, month, year));
+            let shown: Vec<NaiveDate> = grid.iter().flatten().flatten().copied().collect();
+            assert_eq!(shown.len(), days);
+            assert_eq!(shown.first(), Some(&date(1, month, year)));
+        }
+    }
+}
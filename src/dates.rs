@@ -0,0 +1,352 @@
+// Interpretação das datas digitadas pelo usuário.
+//
+// Ordem de precedência ao ler uma data digitada ou colada (`parse_date_localized`):
+// 1. os formatos da aplicação, com o dia primeiro (DD/MM/AAAA, DD/MM/AA,
+//    DD-MM-AAAA e DD.MM.AAAA), e o ISO 8601 (AAAA-MM-DD), que não é ambíguo;
+// 2. a ordem de data do sistema operacional, quando ela é outra (MM/DD/AAAA nos
+//    Estados Unidos, AAAA/MM/DD no Japão...), com '/', '.' ou '-' como separador.
+// Assim, uma data ambígua como "05/06/2024" é sempre 5 de junho, e a ordem do
+// sistema só resolve datas que os formatos da aplicação não aceitam ("05/13/2024").
+// A ordem do sistema vem das variáveis de ambiente LC_ALL, LC_TIME e LANG, nessa
+// ordem; sem elas (como costuma ser no Windows), valem apenas os formatos da aplicação.
+use chrono::{Datelike, NaiveDate};
+use std::sync::OnceLock;
+
+/// Formato de data exibido e aceito pela aplicação.
+pub const DATE_FORMAT: &str = "%d/%m/%Y";
+
+/// Converte um ano de dois dígitos em ano completo usando uma janela deslizante:
+/// anos até o ano atual (nos dois últimos dígitos) pertencem ao século atual e os
+/// demais ao século anterior. Para bebês, "23" vira 2023, e não 1923 ou 0023.
+pub fn expand_two_digit_year(two_digit_year: i32, today: NaiveDate) -> i32 {
+    let century = today.year() / 100 * 100;
+    if two_digit_year <= today.year() % 100 {
+        century + two_digit_year
+    } else {
+        century - 100 + two_digit_year
+    }
+}
+
+/// Interpreta uma data tentando, nesta ordem, os formatos DD/MM/AAAA (também com
+/// anos de dois dígitos, DD/MM/AA, expandidos por `expand_two_digit_year`),
+/// AAAA-MM-DD (ISO 8601, usado pelos sistemas de laboratório), DD-MM-AAAA e
+/// DD.MM.AAAA. Vale o primeiro que der uma data válida.
+pub fn parse_date(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let input = input.trim();
+    parse_day_first(input, '/', today)
+        .or_else(|| parse_iso_date(input))
+        .or_else(|| parse_day_first(input, '-', today))
+        .or_else(|| parse_day_first(input, '.', today))
+}
+
+/// Interpreta uma data com o dia primeiro e `separator` entre as partes. Anos com
+/// dois dígitos só são aceitos com '/', para que "24-03-05" não seja lido como
+/// 24/03/2005 em vez de uma data ISO incompleta.
+fn parse_day_first(input: &str, separator: char, today: NaiveDate) -> Option<NaiveDate> {
+    let parts: Vec<&str> = input.split(separator).collect();
+    let [day, month, year] = parts.as_slice() else {
+        return None;
+    };
+    if separator == '/' && year.len() == 2 && year.chars().all(|c| c.is_ascii_digit()) {
+        let year = expand_two_digit_year(year.parse().ok()?, today);
+        return NaiveDate::parse_from_str(&format!("{}/{}/{}", day, month, year), DATE_FORMAT).ok();
+    }
+    // O '%Y' do chrono aceita anos com qualquer número de dígitos; exige quatro
+    // para que "15/03/023" não seja lido como o ano 23.
+    if year.len() != 4 {
+        return None;
+    }
+    NaiveDate::parse_from_str(&format!("{}/{}/{}", day, month, year), DATE_FORMAT).ok()
+}
+
+/// Ordem do dia, do mês e do ano em uma data escrita com separadores.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DateOrder {
+    DayMonthYear,
+    MonthDayYear,
+    YearMonthDay,
+}
+
+/// Regiões em que as datas são escritas com o mês antes do dia.
+const MONTH_FIRST_REGIONS: [&str; 11] = [
+    "US", "PH", "PR", "GU", "AS", "VI", "UM", "MP", "FM", "MH", "PW",
+];
+
+/// Regiões em que as datas são escritas a partir do ano.
+const YEAR_FIRST_REGIONS: [&str; 7] = ["CN", "JP", "KR", "TW", "HU", "LT", "MN"];
+
+/// Ordem de data do sistema operacional, lida uma vez por execução. `None` quando
+/// o idioma do sistema não pode ser consultado.
+pub fn system_date_order() -> Option<DateOrder> {
+    static ORDER: OnceLock<Option<DateOrder>> = OnceLock::new();
+    *ORDER.get_or_init(|| {
+        let locale = ["LC_ALL", "LC_TIME", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())?;
+        let order = order_from_locale(&locale);
+        log::debug!("Idioma do sistema \"{}\": datas em {:?}", locale, order);
+        order
+    })
+}
+
+/// Ordem de data de um identificador de idioma como "en_US.UTF-8" ou "ja-JP", pela
+/// região. Sem região (como em "C" ou "POSIX"), não há ordem conhecida.
+fn order_from_locale(locale: &str) -> Option<DateOrder> {
+    let name = locale.split(['.', '@']).next()?;
+    let region = name.split(['_', '-']).nth(1)?.to_uppercase();
+    Some(if MONTH_FIRST_REGIONS.contains(&region.as_str()) {
+        DateOrder::MonthDayYear
+    } else if YEAR_FIRST_REGIONS.contains(&region.as_str()) {
+        DateOrder::YearMonthDay
+    } else {
+        DateOrder::DayMonthYear
+    })
+}
+
+/// Interpreta uma data pela ordem de precedência descrita no início do arquivo:
+/// os formatos de `parse_date` e, por fim, a ordem de data do sistema (`system_order`).
+pub fn parse_date_localized(
+    input: &str,
+    today: NaiveDate,
+    system_order: Option<DateOrder>,
+) -> Option<NaiveDate> {
+    parse_date(input, today).or_else(|| {
+        let parts: Vec<&str> = input.trim().split(['/', '.', '-']).collect();
+        let [first, second, third] = parts.as_slice() else {
+            return None;
+        };
+        let (day, month, year) = match system_order? {
+            DateOrder::DayMonthYear => (first, second, third),
+            DateOrder::MonthDayYear => (second, first, third),
+            DateOrder::YearMonthDay => (third, second, first),
+        };
+        parse_date(&format!("{}/{}/{}", day, month, year), today)
+    })
+}
+
+/// Interpreta uma data ISO 8601 (AAAA-MM-DD), aceitando mês e dia com um dígito.
+fn parse_iso_date(input: &str) -> Option<NaiveDate> {
+    let parts: Vec<&str> = input.split('-').collect();
+    let [year, month, day] = parts.as_slice() else {
+        return None;
+    };
+    let all_digits = |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+    if year.len() != 4 || month.len() > 2 || day.len() > 2 {
+        return None;
+    }
+    if ![year, month, day].iter().all(|part| all_digits(part)) {
+        return None;
+    }
+    NaiveDate::from_ymd_opt(year.parse().ok()?, month.parse().ok()?, day.parse().ok()?)
+}
+
+/// Reescreve uma data na forma canônica DD/MM/AAAA (com zeros à esquerda e ano
+/// completo), a partir de qualquer formato aceito por `parse_date` ou da ordem de
+/// data do sistema (veja `parse_date_localized`). Retorna `None` se a data não
+/// puder ser interpretada.
+pub fn normalize_date(
+    input: &str,
+    today: NaiveDate,
+    system_order: Option<DateOrder>,
+) -> Option<String> {
+    parse_date_localized(input, today, system_order)
+        .map(|date| date.format(DATE_FORMAT).to_string())
+}
+
+/// Se a data não é válida como DD/MM/AAAA, mas é válida com o dia e o mês
+/// trocados (como em "05/13/2024", no formato americano), retorna a data nessa
+/// outra leitura, para ser oferecida como sugestão ao usuário. Quando a ordem de
+/// data do sistema (`system_order`) já aceita a data, não há o que sugerir: o
+/// cálculo a usa como está.
+pub fn transposed_date(
+    input: &str,
+    today: NaiveDate,
+    system_order: Option<DateOrder>,
+) -> Option<NaiveDate> {
+    if parse_date_localized(input, today, system_order).is_some() {
+        return None;
+    }
+    let input = input.trim().replace(['.', '-'], "/");
+    let parts: Vec<&str> = input.split('/').collect();
+    let [month, day, year] = parts.as_slice() else {
+        return None;
+    };
+    parse_date(&format!("{}/{}/{}", day, month, year), today)
+}
+
+/// Monta uma data a partir de dia, mês e ano, para os testes de todos os módulos.
+#[cfg(test)]
+pub fn date(day: u32, month: u32, year: i32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_digit_years_up_to_the_current_one_are_in_this_century() {
+        let today = date(15, 10, 2026);
+        assert_eq!(expand_two_digit_year(26, today), 2026);
+        assert_eq!(expand_two_digit_year(27, today), 1927);
+        assert_eq!(expand_two_digit_year(0, today), 2000);
+        assert_eq!(expand_two_digit_year(99, today), 1999);
+        // A janela acompanha a data atual: em 2027, "27" passa a ser 2027.
+        assert_eq!(expand_two_digit_year(27, date(1, 1, 2027)), 2027);
+        // Na virada do século, só "00" fica no século novo.
+        assert_eq!(expand_two_digit_year(0, date(1, 1, 2100)), 2100);
+        assert_eq!(expand_two_digit_year(1, date(1, 1, 2100)), 2001);
+    }
+
+    #[test]
+    fn two_digit_years_are_expanded_when_parsing() {
+        let today = date(15, 10, 2026);
+        assert_eq!(parse_date("15/10/26", today), Some(date(15, 10, 2026)));
+        assert_eq!(parse_date("01/01/00", today), Some(date(1, 1, 2000)));
+        assert_eq!(parse_date("31/12/99", today), Some(date(31, 12, 1999)));
+    }
+
+    #[test]
+    fn iso_dates_are_accepted() {
+        let today = date(15, 10, 2026);
+        assert_eq!(parse_date("2024-03-05", today), Some(date(5, 3, 2024)));
+        assert_eq!(parse_date(" 2024-3-5 ", today), Some(date(5, 3, 2024)));
+        assert_eq!(parse_date("2024-12-31", today), Some(date(31, 12, 2024)));
+    }
+
+    #[test]
+    fn each_accepted_format_is_parsed() {
+        let today = date(15, 10, 2026);
+        for input in [
+            "05/03/2024",
+            "2024-03-05",
+            "05-03-2024",
+            "05.03.2024",
+            "5.3.2024",
+        ] {
+            assert_eq!(
+                parse_date(input, today),
+                Some(date(5, 3, 2024)),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn garbage_and_unlisted_formats_are_rejected() {
+        let today = date(15, 10, 2026);
+        for input in [
+            "",
+            "ontem",
+            "05/03",
+            "2024/03/05",
+            "05.03.24",
+            "05-03-24",
+            "31.02.2024",
+            "05 03 2024",
+            "05/03-2024",
+        ] {
+            assert_eq!(parse_date(input, today), None, "{}", input);
+        }
+    }
+
+    #[test]
+    fn invalid_iso_dates_are_rejected() {
+        let today = date(15, 10, 2026);
+        for input in [
+            "2024-02-30",
+            "2024-13-01",
+            "24-03-05",
+            "2024-003-05",
+            "2024-03-",
+        ] {
+            assert_eq!(parse_date(input, today), None, "{}", input);
+        }
+    }
+
+    #[test]
+    fn transposed_day_and_month_are_suggested() {
+        let today = date(15, 10, 2026);
+        let order = Some(DateOrder::DayMonthYear);
+        assert_eq!(
+            transposed_date("05/13/2024", today, order),
+            Some(date(13, 5, 2024))
+        );
+        assert_eq!(
+            transposed_date("2-28-24", today, order),
+            Some(date(28, 2, 2024))
+        );
+        // Datas válidas e datas inválidas nas duas ordens não têm sugestão.
+        assert_eq!(transposed_date("05/12/2024", today, order), None);
+        assert_eq!(transposed_date("13/13/2024", today, order), None);
+        assert_eq!(transposed_date("2024-05-13", today, order), None);
+    }
+
+    #[test]
+    fn dates_read_by_the_system_order_are_not_suggested() {
+        // Com o sistema em MM/DD/AAAA, "05/13/2024" já é lida como 13 de maio.
+        let today = date(15, 10, 2026);
+        let order = Some(DateOrder::MonthDayYear);
+        assert_eq!(
+            parse_date_localized("05/13/2024", today, order),
+            Some(date(13, 5, 2024))
+        );
+        assert_eq!(transposed_date("05/13/2024", today, order), None);
+    }
+
+    #[test]
+    fn iso_dates_are_normalized_to_the_display_format() {
+        let today = date(15, 10, 2026);
+        assert_eq!(
+            normalize_date("2024-3-5", today, None),
+            Some("05/03/2024".to_string())
+        );
+        assert_eq!(
+            normalize_date("5-3-2024", today, None),
+            Some("05/03/2024".to_string())
+        );
+    }
+
+    #[test]
+    fn locale_region_gives_the_date_order() {
+        assert_eq!(
+            order_from_locale("en_US.UTF-8"),
+            Some(DateOrder::MonthDayYear)
+        );
+        assert_eq!(order_from_locale("pt_BR"), Some(DateOrder::DayMonthYear));
+        assert_eq!(
+            order_from_locale("en_GB.UTF-8"),
+            Some(DateOrder::DayMonthYear)
+        );
+        assert_eq!(order_from_locale("ja-JP"), Some(DateOrder::YearMonthDay));
+        assert_eq!(order_from_locale("C"), None);
+        assert_eq!(order_from_locale("POSIX"), None);
+    }
+
+    #[test]
+    fn system_order_only_resolves_dates_the_app_format_rejects() {
+        let today = date(15, 10, 2026);
+        let us = Some(DateOrder::MonthDayYear);
+        assert_eq!(
+            parse_date_localized("05/13/2024", today, us),
+            Some(date(13, 5, 2024))
+        );
+        assert_eq!(
+            parse_date_localized("5.13.24", today, us),
+            Some(date(13, 5, 2024))
+        );
+        // Ambígua: o formato da aplicação tem precedência.
+        assert_eq!(
+            parse_date_localized("05/06/2024", today, us),
+            Some(date(5, 6, 2024))
+        );
+        assert_eq!(
+            parse_date_localized("2024/03/05", today, Some(DateOrder::YearMonthDay)),
+            Some(date(5, 3, 2024))
+        );
+        // Sem a ordem do sistema, vale apenas o formato da aplicação.
+        assert_eq!(parse_date_localized("05/13/2024", today, None), None);
+    }
+}
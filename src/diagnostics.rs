@@ -0,0 +1,70 @@
+// Modo de diagnóstico: registra no 'stderr' como as entradas foram interpretadas e
+// os passos do cálculo, para ajudar a entender um resultado inesperado. Fica
+// desligado a menos que o programa seja iniciado com '--verbose' ou com a variável
+// de ambiente 'CALCULADORA_LOG' (por exemplo, 'CALCULADORA_LOG=debug').
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Variável de ambiente com o nível dos registros: "error", "warn", "info",
+/// "debug" ou "trace".
+const LOG_LEVEL_VAR: &str = "CALCULADORA_LOG";
+
+/// Escreve cada registro em uma linha do 'stderr', com o nível na frente.
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        // Registra apenas as mensagens do próprio programa, e não as do egui.
+        if self.enabled(record.metadata()) && record.target().starts_with(env!("CARGO_CRATE_NAME"))
+        {
+            eprintln!("[{}] {}", record.level(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: StderrLogger = StderrLogger;
+
+/// Nível pedido na linha de comando ou no ambiente; sem pedido, desligado.
+fn requested_level(args: &[String], env_level: Option<&str>) -> LevelFilter {
+    if args.iter().any(|arg| arg == "--verbose") {
+        return LevelFilter::Debug;
+    }
+    env_level
+        .and_then(|level| level.trim().parse().ok())
+        .unwrap_or(LevelFilter::Off)
+}
+
+/// Ativa os registros conforme os argumentos do programa e o ambiente.
+pub fn init() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let level = requested_level(&args, std::env::var(LOG_LEVEL_VAR).ok().as_deref());
+    if level != LevelFilter::Off && log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(level);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logging_is_off_by_default() {
+        assert_eq!(requested_level(&[], None), LevelFilter::Off);
+        assert_eq!(requested_level(&[], Some("nada")), LevelFilter::Off);
+    }
+
+    #[test]
+    fn verbose_flag_and_environment_enable_logging() {
+        assert_eq!(
+            requested_level(&["--verbose".to_string()], None),
+            LevelFilter::Debug
+        );
+        assert_eq!(requested_level(&[], Some("trace")), LevelFilter::Trace);
+        assert_eq!(requested_level(&[], Some(" INFO ")), LevelFilter::Info);
+    }
+}
@@ -0,0 +1,52 @@
+// Sinais de confirmação do cálculo: um lampejo na área de resultados e,
+// opcionalmente, um som curto. O som depende da feature 'sound' (crate 'rodio'),
+// desativada por padrão para não exigir bibliotecas de áudio na compilação.
+
+/// Resultado do cálculo a ser sinalizado.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Cue {
+    Success,
+    Error,
+}
+
+impl Cue {
+    /// Cor do lampejo na área de resultados.
+    pub fn color(self) -> eframe::egui::Color32 {
+        match self {
+            Cue::Success => eframe::egui::Color32::from_rgb(60, 180, 90),
+            Cue::Error => eframe::egui::Color32::from_rgb(220, 60, 60),
+        }
+    }
+}
+
+/// Toca um bipe curto em segundo plano: agudo para sucesso, grave para erro.
+#[cfg(feature = "sound")]
+pub fn play(cue: Cue) {
+    use rodio::Source;
+    use std::time::Duration;
+
+    let frequency = match cue {
+        Cue::Success => 880.0,
+        Cue::Error => 220.0,
+    };
+    // O fluxo de áudio precisa existir até o fim do som, então tudo roda em uma
+    // thread própria. Falhas (sem dispositivo de áudio) são ignoradas.
+    std::thread::spawn(move || {
+        let Ok((_stream, handle)) = rodio::OutputStream::try_default() else {
+            return;
+        };
+        let Ok(sink) = rodio::Sink::try_new(&handle) else {
+            return;
+        };
+        sink.append(
+            rodio::source::SineWave::new(frequency)
+                .take_duration(Duration::from_millis(120))
+                .amplify(0.2),
+        );
+        sink.sleep_until_end();
+    });
+}
+
+/// Sem a feature 'sound', não há som.
+#[cfg(not(feature = "sound"))]
+pub fn play(_cue: Cue) {}
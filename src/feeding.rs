@@ -0,0 +1,106 @@
+// Volume hídrico diário de referência pelo peso e pelo dia de vida, no primeiro
+// mês. Os volumes em mL/kg/dia ficam na tabela abaixo, com a fonte, para facilitar
+// a conferência e a atualização. A tabela é a de manutenção para recém-nascidos a
+// termo; os valores servem apenas de referência, a prescrição segue o protocolo da
+// unidade e os prematuros têm esquemas próprios.
+
+/// Origem da tabela, exibida junto dos volumes.
+pub const SOURCE: &str =
+    "NICE NG29 (2015), necessidades hídricas de manutenção de recém-nascidos a termo";
+
+/// Número de mamadas por dia usado para dividir o volume diário (de 3 em 3 horas).
+pub const FEEDS_PER_DAY: u32 = 8;
+
+/// Último dia de vida coberto pela tabela.
+pub const LAST_DAY: i64 = 28;
+
+/// Faixa de volume, em mL/kg/dia, de um intervalo de dias de vida.
+struct ScheduleRow {
+    first_day: i64,
+    ml_per_kg: (u32, u32),
+}
+
+/// Volumes por dia de vida (o nascimento é o 1º dia); cada linha vale até a
+/// seguinte, e a última até `LAST_DAY`.
+const SCHEDULE: &[ScheduleRow] = &[
+    ScheduleRow {
+        first_day: 1,
+        ml_per_kg: (50, 60),
+    },
+    ScheduleRow {
+        first_day: 2,
+        ml_per_kg: (70, 80),
+    },
+    ScheduleRow {
+        first_day: 3,
+        ml_per_kg: (80, 100),
+    },
+    ScheduleRow {
+        first_day: 4,
+        ml_per_kg: (100, 120),
+    },
+    ScheduleRow {
+        first_day: 5,
+        ml_per_kg: (120, 150),
+    },
+];
+
+/// Volume de referência para um dia de vida e um peso.
+#[derive(Debug, PartialEq)]
+pub struct DailyVolume {
+    pub ml_per_kg: (u32, u32),
+    /// Volume do dia, em mL, arredondado.
+    pub ml_per_day: (u32, u32),
+    /// Volume de cada uma das `FEEDS_PER_DAY` mamadas, em mL, arredondado.
+    pub ml_per_feed: (u32, u32),
+}
+
+/// Volume de referência no dia de vida `day_of_life` para um bebê de
+/// `weight_grams`. Fora do primeiro mês, retorna `None`.
+pub fn daily_volume(day_of_life: i64, weight_grams: f64) -> Option<DailyVolume> {
+    if !(1..=LAST_DAY).contains(&day_of_life) {
+        return None;
+    }
+    let row = SCHEDULE
+        .iter()
+        .rev()
+        .find(|row| row.first_day <= day_of_life)?;
+    let per_day = |ml_per_kg: u32| ml_per_kg as f64 * weight_grams / 1000.0;
+    let (min, max) = (per_day(row.ml_per_kg.0), per_day(row.ml_per_kg.1));
+    let per_feed = |ml: f64| (ml / FEEDS_PER_DAY as f64).round() as u32;
+    Some(DailyVolume {
+        ml_per_kg: row.ml_per_kg,
+        ml_per_day: (min.round() as u32, max.round() as u32),
+        ml_per_feed: (per_feed(min), per_feed(max)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn volumes_follow_the_day_of_life_and_the_weight() {
+        assert_eq!(
+            daily_volume(1, 3000.0),
+            Some(DailyVolume {
+                ml_per_kg: (50, 60),
+                ml_per_day: (150, 180),
+                ml_per_feed: (19, 23),
+            })
+        );
+        assert_eq!(daily_volume(4, 2500.0).unwrap().ml_per_day, (250, 300));
+        // Do 5º ao 28º dia, a faixa é a mesma.
+        assert_eq!(daily_volume(5, 3500.0).unwrap().ml_per_day, (420, 525));
+        assert_eq!(
+            daily_volume(LAST_DAY, 3500.0).unwrap().ml_per_kg,
+            (120, 150)
+        );
+    }
+
+    #[test]
+    fn days_outside_the_first_month_have_no_reference() {
+        assert_eq!(daily_volume(0, 3000.0), None);
+        assert_eq!(daily_volume(LAST_DAY + 1, 3000.0), None);
+    }
+}
@@ -0,0 +1,167 @@
+// Exportação das métricas do cálculo em um formato simplificado inspirado no
+// recurso Observation do HL7 FHIR, para importação em prontuários eletrônicos.
+//
+// O arquivo é uma lista JSON com um objeto por métrica, sempre com estes campos:
+//
+//     {
+//       "resourceType": "Observation",
+//       "status": "final",
+//       "code": { "text": "idade-corrigida" },
+//       "valueQuantity": { "value": 79, "unit": "d" },
+//       "effectiveDate": "2024-05-15"
+//     }
+//
+// - 'code.text' identifica a métrica (veja os códigos em 'report_metrics', em main.rs);
+// - 'valueQuantity.value' é sempre um número inteiro e 'unit' uma unidade UCUM
+//   ("d" para dias);
+// - 'effectiveDate' é a data de referência do cálculo, no formato AAAA-MM-DD;
+// - em gestações múltiplas, cada bebê tem o seu conjunto de observações, e cada
+//   uma traz também 'subject.display' com o bebê ("Bebê 2"). Com um bebê só, o
+//   campo não aparece.
+use chrono::NaiveDate;
+use serde::Serialize;
+
+/// Métrica a ser exportada: código, valor inteiro e unidade UCUM.
+pub struct Metric {
+    pub code: &'static str,
+    pub value: i64,
+    pub unit: &'static str,
+}
+
+#[derive(Serialize)]
+struct Code {
+    text: &'static str,
+}
+
+#[derive(Serialize)]
+struct Subject {
+    display: String,
+}
+
+#[derive(Serialize)]
+struct Quantity {
+    value: i64,
+    unit: &'static str,
+}
+
+/// Observação no formato documentado no início deste arquivo.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Observation {
+    resource_type: &'static str,
+    status: &'static str,
+    code: Code,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subject: Option<Subject>,
+    value_quantity: Quantity,
+    effective_date: String,
+}
+
+/// Converte as métricas em observações com a data de referência `effective_date`
+/// e, em gestações múltiplas, o bebê a que se referem (`subject`).
+pub fn observations(
+    metrics: &[Metric],
+    effective_date: NaiveDate,
+    subject: Option<String>,
+) -> Vec<Observation> {
+    metrics
+        .iter()
+        .map(|metric| Observation {
+            resource_type: "Observation",
+            status: "final",
+            code: Code { text: metric.code },
+            subject: subject.clone().map(|display| Subject { display }),
+            value_quantity: Quantity {
+                value: metric.value,
+                unit: metric.unit,
+            },
+            effective_date: effective_date.format("%Y-%m-%d").to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    /// Confere um objeto contra o formato documentado: exatamente os campos
+    /// descritos, com os tipos esperados.
+    fn assert_matches_schema(observation: &Value) {
+        let fields = observation
+            .as_object()
+            .expect("cada observação é um objeto");
+        let mut keys: Vec<&str> = fields
+            .keys()
+            .map(String::as_str)
+            .filter(|&key| key != "subject")
+            .collect();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            [
+                "code",
+                "effectiveDate",
+                "resourceType",
+                "status",
+                "valueQuantity"
+            ]
+        );
+        assert_eq!(observation["resourceType"], "Observation");
+        assert_eq!(observation["status"], "final");
+        assert!(observation["code"]["text"].is_string());
+        assert_eq!(observation["code"].as_object().unwrap().len(), 1);
+        assert!(observation["valueQuantity"]["value"].is_i64());
+        assert!(observation["valueQuantity"]["unit"].is_string());
+        if let Some(subject) = fields.get("subject") {
+            assert!(subject["display"].is_string());
+            assert_eq!(subject.as_object().unwrap().len(), 1);
+        }
+        assert_eq!(observation["valueQuantity"].as_object().unwrap().len(), 2);
+        let date = observation["effectiveDate"].as_str().unwrap();
+        assert!(
+            NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok(),
+            "{}",
+            date
+        );
+    }
+
+    #[test]
+    fn output_matches_documented_shape() {
+        let metrics = [
+            Metric {
+                code: "idade-corrigida",
+                value: 79,
+                unit: "d",
+            },
+            Metric {
+                code: "idade-cronologica",
+                value: 135,
+                unit: "d",
+            },
+        ];
+        let date = NaiveDate::from_ymd_opt(2024, 5, 15).unwrap();
+        let json = serde_json::to_value(observations(&metrics, date, None)).unwrap();
+        let list = json.as_array().expect("o arquivo é uma lista");
+        assert_eq!(list.len(), 2);
+        list.iter().for_each(assert_matches_schema);
+        assert!(list[0].get("subject").is_none());
+        assert_eq!(list[0]["code"]["text"], "idade-corrigida");
+        assert_eq!(list[0]["valueQuantity"]["value"], 79);
+        assert_eq!(list[0]["effectiveDate"], "2024-05-15");
+    }
+
+    #[test]
+    fn each_baby_of_a_multiple_birth_is_the_subject_of_its_observations() {
+        let metrics = [Metric {
+            code: "idade-corrigida",
+            value: 79,
+            unit: "d",
+        }];
+        let date = NaiveDate::from_ymd_opt(2024, 5, 15).unwrap();
+        let json =
+            serde_json::to_value(observations(&metrics, date, Some("Bebê 2".to_string()))).unwrap();
+        assert_matches_schema(&json[0]);
+        assert_eq!(json[0]["subject"]["display"], "Bebê 2");
+    }
+}
@@ -0,0 +1,44 @@
+// Diálogos de abrir e salvar arquivos. Na versão de desktop, são os diálogos
+// nativos da 'rfd'. No navegador não há diálogos síncronos nem acesso ao sistema
+// de arquivos, então todos retornam 'None' e as ações com arquivos não fazem nada.
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use rfd::FileDialog;
+
+#[cfg(target_arch = "wasm32")]
+pub use web::FileDialog;
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use std::path::PathBuf;
+
+    /// Diálogo sem efeito, com a mesma interface da 'rfd::FileDialog'.
+    #[derive(Default)]
+    pub struct FileDialog;
+
+    impl FileDialog {
+        pub fn new() -> Self {
+            FileDialog
+        }
+
+        pub fn add_filter(self, _name: impl Into<String>, _extensions: &[impl ToString]) -> Self {
+            self
+        }
+
+        pub fn set_file_name(self, _file_name: impl Into<String>) -> Self {
+            self
+        }
+
+        pub fn pick_file(self) -> Option<PathBuf> {
+            None
+        }
+
+        pub fn pick_folder(self) -> Option<PathBuf> {
+            None
+        }
+
+        pub fn save_file(self) -> Option<PathBuf> {
+            None
+        }
+    }
+}
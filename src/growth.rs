@@ -0,0 +1,248 @@
+// Percentil aproximado do peso pelas curvas de Fenton (2013) para prematuros, pela
+// idade pós-menstrual. A tabela guarda os percentis 3, 10, 50, 90 e 97 a cada duas
+// semanas, de 22 a 50 semanas, lidos das curvas publicadas e arredondados; entre
+// os pontos, o peso é interpolado linearmente. O resultado serve de orientação e
+// não substitui o gráfico oficial.
+use serde::{Deserialize, Serialize};
+
+/// Sexo do bebê, que define a curva usada. No formulário, fica como `Option<Sex>`:
+/// `None` é "Não informado".
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Sex {
+    Male,
+    Female,
+}
+
+impl Sex {
+    /// Nome exibido na interface, também para quando o sexo não foi informado.
+    pub fn label(sex: Option<Sex>) -> &'static str {
+        match sex {
+            Some(Sex::Male) => "Masculino",
+            Some(Sex::Female) => "Feminino",
+            None => "Não informado",
+        }
+    }
+}
+
+/// Versão das curvas lidas na tabela abaixo, exibida em "Ajuda > Sobre".
+pub const TABLE_VERSION: &str = "Fenton 2013";
+
+/// Versão mais recente das curvas de que se tem notícia. Quando for outra que não
+/// `TABLE_VERSION`, a interface avisa que a tabela embutida está desatualizada;
+/// atualize as duas juntas ao trocar a tabela.
+pub const LATEST_KNOWN_VERSION: &str = "Fenton 2013";
+
+/// A versão mais nova das curvas, se a tabela embutida não for a mais recente.
+pub fn newer_version() -> Option<&'static str> {
+    newer_than(TABLE_VERSION, LATEST_KNOWN_VERSION)
+}
+
+fn newer_than<'a>(in_use: &str, latest: &'a str) -> Option<&'a str> {
+    (latest != in_use).then_some(latest)
+}
+
+/// Percentis de referência de cada linha da tabela.
+const PERCENTILES: [f64; 5] = [3.0, 10.0, 50.0, 90.0, 97.0];
+
+/// Escores z dos percentis de `PERCENTILES`.
+const Z_SCORES: [f64; 5] = [-1.881, -1.2816, 0.0, 1.2816, 1.881];
+
+/// Primeira e última idade pós-menstrual da tabela, em semanas.
+pub const FIRST_WEEK: u32 = 22;
+pub const LAST_WEEK: u32 = 50;
+
+/// Pesos (g) nos percentis 3, 10, 50, 90 e 97, a cada duas semanas a partir de
+/// `FIRST_WEEK`.
+const BOYS: [[f64; 5]; 15] = [
+    [350.0, 400.0, 500.0, 610.0, 670.0],
+    [450.0, 520.0, 650.0, 790.0, 860.0],
+    [580.0, 670.0, 850.0, 1030.0, 1120.0],
+    [750.0, 880.0, 1100.0, 1350.0, 1480.0],
+    [970.0, 1130.0, 1400.0, 1700.0, 1860.0],
+    [1230.0, 1420.0, 1750.0, 2100.0, 2280.0],
+    [1560.0, 1790.0, 2200.0, 2600.0, 2810.0],
+    [1930.0, 2190.0, 2650.0, 3100.0, 3330.0],
+    [2330.0, 2600.0, 3100.0, 3600.0, 3850.0],
+    [2700.0, 2980.0, 3500.0, 4050.0, 4300.0],
+    [3050.0, 3350.0, 3900.0, 4500.0, 4800.0],
+    [3500.0, 3850.0, 4500.0, 5150.0, 5500.0],
+    [3950.0, 4350.0, 5050.0, 5750.0, 6100.0],
+    [4350.0, 4800.0, 5550.0, 6300.0, 6700.0],
+    [4750.0, 5200.0, 6050.0, 6850.0, 7250.0],
+];
+
+const GIRLS: [[f64; 5]; 15] = [
+    [330.0, 380.0, 480.0, 580.0, 640.0],
+    [420.0, 490.0, 610.0, 750.0, 820.0],
+    [540.0, 630.0, 800.0, 980.0, 1070.0],
+    [700.0, 820.0, 1030.0, 1270.0, 1400.0],
+    [900.0, 1060.0, 1320.0, 1610.0, 1770.0],
+    [1150.0, 1340.0, 1660.0, 2000.0, 2180.0],
+    [1470.0, 1700.0, 2100.0, 2500.0, 2710.0],
+    [1830.0, 2090.0, 2550.0, 3000.0, 3230.0],
+    [2220.0, 2490.0, 2990.0, 3480.0, 3730.0],
+    [2580.0, 2860.0, 3380.0, 3920.0, 4180.0],
+    [2900.0, 3200.0, 3750.0, 4330.0, 4620.0],
+    [3300.0, 3650.0, 4250.0, 4900.0, 5220.0],
+    [3700.0, 4080.0, 4750.0, 5450.0, 5800.0],
+    [4080.0, 4480.0, 5200.0, 5950.0, 6330.0],
+    [4450.0, 4880.0, 5650.0, 6450.0, 6850.0],
+];
+
+/// Motivo pelo qual o percentil não foi calculado.
+#[derive(Debug, PartialEq)]
+pub enum NoPercentile {
+    /// Idade pós-menstrual fora da faixa da tabela (22 a 50 semanas).
+    OutOfRange,
+    /// Entrada degenerada (peso não finito ou pesos de referência que não crescem
+    /// com o percentil), que daria NaN ou infinito na interpolação.
+    NotComputable,
+}
+
+/// Percentil aproximado (entre 0,1 e 99,9) de um peso de `grams` gramas na idade
+/// pós-menstrual de `post_menstrual_days` dias.
+pub fn fenton_percentile(
+    post_menstrual_days: i64,
+    sex: Sex,
+    grams: f64,
+) -> Result<f64, NoPercentile> {
+    let weeks = post_menstrual_days as f64 / 7.0;
+    if weeks < FIRST_WEEK as f64 || weeks > LAST_WEEK as f64 {
+        return Err(NoPercentile::OutOfRange);
+    }
+    let table = match sex {
+        Sex::Male => &BOYS,
+        Sex::Female => &GIRLS,
+    };
+    // Pesos de referência na idade exata, interpolados entre as linhas vizinhas.
+    let position = (weeks - FIRST_WEEK as f64) / 2.0;
+    let row = (position.floor() as usize).min(table.len() - 2);
+    let fraction = position - row as f64;
+    let reference: Vec<f64> = (0..PERCENTILES.len())
+        .map(|i| table[row][i] + (table[row + 1][i] - table[row][i]) * fraction)
+        .collect();
+    percentile_from_reference(&reference, grams)
+}
+
+/// Percentil de um peso a partir dos pesos de referência nos percentis de
+/// `PERCENTILES`. Entradas que dariam uma divisão por zero ou um resultado não
+/// finito são recusadas em vez de virar "NaN" na interface.
+fn percentile_from_reference(reference: &[f64], grams: f64) -> Result<f64, NoPercentile> {
+    if !grams.is_finite()
+        || !reference.iter().all(|weight| weight.is_finite())
+        || reference.windows(2).any(|pair| pair[1] <= pair[0])
+    {
+        return Err(NoPercentile::NotComputable);
+    }
+
+    // O peso é convertido em escore z entre os percentis vizinhos (ou pelo segmento
+    // mais próximo, além dos extremos) e o escore z, de volta em percentil.
+    let segment = reference
+        .windows(2)
+        .position(|pair| grams <= pair[1])
+        .unwrap_or(reference.len() - 2);
+    let (w0, w1) = (reference[segment], reference[segment + 1]);
+    let (z0, z1) = (Z_SCORES[segment], Z_SCORES[segment + 1]);
+    let z = z0 + (grams - w0) / (w1 - w0) * (z1 - z0);
+    let percentile = normal_cdf(z) * 100.0;
+    if !percentile.is_finite() {
+        return Err(NoPercentile::NotComputable);
+    }
+    Ok(percentile.clamp(0.1, 99.9))
+}
+
+/// Função de distribuição acumulada da normal padrão, pela aproximação de
+/// Abramowitz e Stegun (erro menor que 1e-7).
+fn normal_cdf(z: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.231_641_9 * z.abs());
+    let density = (-z * z / 2.0).exp() / (2.0 * std::f64::consts::PI).sqrt();
+    let tail = density
+        * t
+        * (0.319_381_530
+            + t * (-0.356_563_782
+                + t * (1.781_477_937 + t * (-1.821_255_978 + t * 1.330_274_429))));
+    if z >= 0.0 {
+        1.0 - tail
+    } else {
+        tail
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_newer_table_version_is_reported() {
+        assert_eq!(newer_than("Fenton 2013", "Fenton 2013"), None);
+        assert_eq!(
+            newer_than("Fenton 2013", "Fenton 2025"),
+            Some("Fenton 2025")
+        );
+    }
+
+    #[test]
+    fn reference_weights_fall_on_their_percentiles() {
+        // 32 semanas (224 dias), exatamente sobre uma linha da tabela.
+        for (grams, expected) in [(1750.0, 50.0), (1420.0, 10.0), (2280.0, 97.0)] {
+            let percentile = fenton_percentile(224, Sex::Male, grams).unwrap();
+            assert!(
+                (percentile - expected).abs() < 0.5,
+                "{} g: {}",
+                grams,
+                percentile
+            );
+        }
+        // O mesmo peso fica em um percentil maior na curva das meninas.
+        assert!(fenton_percentile(224, Sex::Female, 1750.0).unwrap() > 50.0);
+    }
+
+    #[test]
+    fn weights_between_rows_are_interpolated() {
+        // 33 semanas: mediana entre 1750 g (32) e 2200 g (34).
+        let percentile = fenton_percentile(231, Sex::Male, 1975.0).unwrap();
+        assert!((percentile - 50.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn ages_outside_the_chart_have_no_percentile() {
+        assert_eq!(
+            fenton_percentile(21 * 7 + 6, Sex::Male, 500.0),
+            Err(NoPercentile::OutOfRange)
+        );
+        assert_eq!(
+            fenton_percentile(50 * 7 + 1, Sex::Female, 6000.0),
+            Err(NoPercentile::OutOfRange)
+        );
+        assert_eq!(fenton_percentile(50 * 7, Sex::Female, 200.0), Ok(0.1));
+    }
+
+    #[test]
+    fn degenerate_inputs_are_not_computable() {
+        for grams in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert_eq!(
+                fenton_percentile(224, Sex::Male, grams),
+                Err(NoPercentile::NotComputable)
+            );
+        }
+        // Sem dispersão entre os percentis (divisão por zero) ou com dispersão
+        // negativa, não há escore z que faça sentido.
+        for reference in [
+            [1000.0; 5],
+            [1000.0, 1000.0, 1500.0, 2000.0, 2500.0],
+            [2500.0, 2000.0, 1500.0, 1000.0, 500.0],
+            [f64::NAN, 1000.0, 1500.0, 2000.0, 2500.0],
+        ] {
+            assert_eq!(
+                percentile_from_reference(&reference, 1500.0),
+                Err(NoPercentile::NotComputable),
+                "{:?}",
+                reference
+            );
+        }
+        assert!(
+            percentile_from_reference(&[500.0, 1000.0, 1500.0, 2000.0, 2500.0], 1500.0)
+                .is_ok_and(f64::is_finite)
+        );
+    }
+}
@@ -0,0 +1,651 @@
+// Tradução da interface. Os textos são escritos em português no código e servem
+// de chave: `t` devolve a tradução do texto para o idioma escolhido ou, se ela não
+// existir, o próprio texto em português. Textos com valores marcam cada valor com
+// "{}" e são preenchidos por `tf`, na ordem dos argumentos; a tradução deve manter
+// a mesma quantidade e a mesma ordem dos valores.
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// Idioma da interface.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Lang {
+    #[default]
+    PtBr,
+    En,
+    /// Tradução parcial: os textos ainda sem tradução aparecem em português.
+    Es,
+}
+
+impl Lang {
+    /// Idiomas disponíveis, na ordem da lista de escolha.
+    pub const ALL: [Lang; 3] = [Lang::PtBr, Lang::En, Lang::Es];
+
+    /// Nome do idioma, escrito no próprio idioma para que cada um reconheça o seu.
+    pub fn label(self) -> &'static str {
+        match self {
+            Lang::PtBr => "Português (Brasil)",
+            Lang::En => "English",
+            Lang::Es => "Español",
+        }
+    }
+}
+
+/// Traduz um texto da interface para `lang`.
+pub fn t(key: &str, lang: Lang) -> &str {
+    let table = match lang {
+        Lang::PtBr => return key,
+        Lang::En => ENGLISH,
+        Lang::Es => SPANISH,
+    };
+    match table.iter().find(|(pt, _)| *pt == key) {
+        Some((_, translated)) => translated,
+        None => {
+            log::trace!("Texto sem tradução para {:?}: {}", lang, key);
+            key
+        }
+    }
+}
+
+/// Traduz um texto com valores e preenche cada "{}" com os argumentos, em ordem.
+pub fn tf(key: &str, lang: Lang, args: &[&dyn Display]) -> String {
+    let mut parts = t(key, lang).split("{}");
+    let mut text = parts.next().unwrap_or_default().to_string();
+    for (index, part) in parts.enumerate() {
+        if let Some(arg) = args.get(index) {
+            text.push_str(&arg.to_string());
+        }
+        text.push_str(part);
+    }
+    text
+}
+
+/// Textos em inglês, pelo texto em português.
+const ENGLISH: &[(&str, &str)] = &[
+    ("Falha ao copiar como {}: formato não suportado pela área de transferência deste sistema.", "Failed to copy as {}: format not supported by this system's clipboard."),
+    ("Falha ao copiar como {}: a área de transferência está em uso por outro programa. Tente novamente.", "Failed to copy as {}: the clipboard is in use by another program. Try again."),
+    ("Falha ao copiar como {}: não foi possível converter o resultado.", "Failed to copy as {}: the result could not be converted."),
+    ("Falha ao copiar como {}: erro inesperado ({}).", "Failed to copy as {}: unexpected error ({})."),
+    ("Falha ao copiar como {}: a área de transferência não respondeu.", "Failed to copy as {}: the clipboard did not respond."),
+    ("Escolher no calendário", "Pick from calendar"),
+    ("{} de {}", "{} {}"),
+    ("Hoje", "Today"),
+    ("Calculadora de Idade Gestacional do Bebê", "Baby Gestational Age Calculator"),
+    ("Nenhum campo precisou ser alterado.", "No field needed changes."),
+    ("Campos normalizados:\n{}", "Normalized fields:\n{}"),
+    ("Formato da data de referência inválido. Use DD/MM/AAAA.", "Invalid reference date format. Use DD/MM/YYYY."),
+    ("Formato de data inválido. Use DD/MM/AAAA.", "Invalid date format. Use DD/MM/YYYY."),
+    ("Os dias já foram informados no campo de semanas; deixe o campo de dias vazio.", "The days were already given in the weeks field; leave the days field empty."),
+    ("Formato da DUM inválido. Use DD/MM/AAAA.", "Invalid LMP format. Use DD/MM/YYYY."),
+    ("Atenção: prematuro extremo ({} semanas), no limite da viabilidade. Interprete a idade corrigida com cautela.", "Warning: extremely preterm ({} weeks), at the limit of viability. Interpret the corrected age with caution."),
+    ("A DUM não pode ser posterior à data de nascimento.", "The LMP cannot be after the date of birth."),
+    ("A DUM indica {} semanas e {} dias ao nascer, {} dias de diferença da idade gestacional informada. Confira os dados.", "The LMP indicates {} weeks and {} days at birth, {} days off from the gestational age entered. Check the data."),
+    ("A área de transferência não aceitou acentos: o texto foi copiado sem eles.", "The clipboard did not accept accented characters: the text was copied without them."),
+    ("Área de transferência não disponível.", "Clipboard not available."),
+    ("-7 dias", "-7 days"),
+    ("Gêmeos", "Twins"),
+    ("Tamanho do texto:", "Text size:"),
+    ("Peso atual (g, opcional):", "Current weight (g, optional):"),
+    ("Com o sexo, mostra o percentil aproximado do peso nas curvas de Fenton.", "Together with the sex, shows the approximate weight percentile on the Fenton charts."),
+    ("Sexo:", "Sex:"),
+    ("Sexo", "Sex"),
+    ("Masculino", "Male"),
+    ("Feminino", "Female"),
+    ("Não informado", "Not stated"),
+    ("informado no formulário; usado nos percentis de crescimento", "entered in the form; used for the growth percentiles"),
+    ("Sexo não informado: o percentil do peso não pode ser calculado.", "Sex not stated: the weight percentile cannot be calculated."),
+    ("Peso inválido. Informe o peso em gramas, entre {} e {}.", "Invalid weight. Enter the weight in grams, between {} and {}."),
+    ("Peso no percentil ~{} (Fenton)", "Weight at percentile ~{} (Fenton)"),
+    ("Percentil do peso: não calculável.", "Weight percentile: not computable."),
+    ("Percentil do peso indisponível: as curvas de Fenton vão de {} a {} semanas de idade pós-menstrual.", "Weight percentile unavailable: the Fenton charts cover {} to {} weeks of postmenstrual age."),
+    ("Valor aproximado, interpolado das curvas de Fenton (2013) pela idade pós-menstrual. Confira no gráfico oficial.", "Approximate value, interpolated from the Fenton (2013) charts by postmenstrual age. Check the official chart."),
+    ("Avançado", "Advanced"),
+    ("Corrigir a prematuridade para:", "Correct prematurity to:"),
+    ("37 semanas", "37 weeks"),
+    ("38 semanas", "38 weeks"),
+    ("40 semanas (data provável do parto)", "40 weeks (estimated due date)"),
+    ("Siga o protocolo do serviço. A data provável do parto continua sendo calculada para 40 semanas.", "Follow your service's protocol. The estimated due date is still calculated for 40 weeks."),
+    ("Fonte da idade gestacional, quando a DUM é informada:", "Gestational age source, when the LMP is entered:"),
+    ("Semanas e dias informados, depois a DUM", "Entered weeks and days, then the LMP"),
+    ("DUM, depois as semanas e dias informados", "LMP, then the entered weeks and days"),
+    ("O cálculo usa a primeira fonte válida; a usada aparece nos detalhes do cálculo.", "The calculation uses the first valid source; the one used is shown in the calculation details."),
+    ("Fonte da IG", "GA source"),
+    ("Mostrar o volume hídrico de referência no primeiro mês (requer o peso)", "Show the reference fluid volume in the first month (requires the weight)"),
+    ("Volume hídrico de referência", "Reference fluid volume"),
+    ("Fonte: {}", "Source: {}"),
+    ("{}º dia de vida: {} a {} mL/kg/dia", "Day of life {}: {} to {} mL/kg/day"),
+    ("Com {} g: {} a {} mL por dia", "At {} g: {} to {} mL per day"),
+    ("{} a {} mL por mamada, em {} mamadas ao dia", "{} to {} mL per feed, in {} feeds a day"),
+    ("A referência cobre apenas até o {}º dia de vida.", "The reference only covers up to day of life {}."),
+    ("Apenas referência, para recém-nascidos a termo: não substitui a prescrição. Prematuros e bebês com restrição hídrica seguem o protocolo da unidade.", "Reference only, for term newborns: it does not replace the prescription. Preterm babies and babies on fluid restriction follow the unit's protocol."),
+    ("NICE NG29 (2015), necessidades hídricas de manutenção de recém-nascidos a termo", "NICE NG29 (2015), maintenance fluid requirements of term newborns"),
+    ("Ajuda", "Help"),
+    ("Sobre...", "About..."),
+    ("Sobre", "About"),
+    ("Versão {}", "Version {}"),
+    ("Curvas de crescimento: {}", "Growth charts: {}"),
+    ("Versão mais recente conhecida: {}", "Latest known version: {}"),
+    ("Os resultados servem de orientação e não substituem a avaliação clínica.", "The results are for guidance and do not replace clinical assessment."),
+    ("Percentil pelas curvas {}; já existe a versão {}.", "Percentile from the {} charts; version {} is already available."),
+    ("Dispensar o aviso", "Dismiss the notice"),
+    ("semanas e dias informados", "entered weeks and days"),
+    ("a primeira fonte válida na ordem de prioridade das configurações", "the first valid source in the priority order from the settings"),
+    ("Indicar o termo de referência também quando for 40 semanas", "Show the reference term also when it is 40 weeks"),
+    ("Termo de referência: {} semanas", "Reference term: {} weeks"),
+    ("Mostrar a idade corrigida em um ícone na bandeja do sistema", "Show the corrected age in a system tray icon"),
+    ("A idade corrigida de hoje do último cálculo aparece ao passar o mouse sobre o ícone e é atualizada a cada dia.", "Today's corrected age from the last calculation appears when hovering over the icon and is updated daily."),
+    ("Indisponível nesta versão: o ícone na bandeja exige a feature 'tray' e o Windows ou o macOS.", "Unavailable in this build: the tray icon requires the 'tray' feature and Windows or macOS."),
+    ("Altere em Opções > Configurações > Avançado.", "Change it in Options > Settings > Advanced."),
+    ("Dia em que o bebê nasceu.", "Day the baby was born."),
+    ("Semanas completas de gestação ao nascer, conforme o obstetra ({} é o termo).", "Completed weeks of pregnancy at birth, as stated by the obstetrician ({} is term)."),
+    ("Dias além das semanas completas, de 0 a 6 (ex.: 32 semanas e 4 dias).", "Days beyond the completed weeks, from 0 to 6 (e.g. 32 weeks and 4 days)."),
+    ("Idade corrigida: a idade descontando as semanas que faltaram para as {} semanas de gestação, usada para avaliar o desenvolvimento do prematuro.", "Corrected age: the age minus the weeks the pregnancy fell short of {} weeks, used to assess a preterm baby's development."),
+    ("Trigêmeos", "Triplets"),
+    ("Calcula para os outros bebês com a mesma idade gestacional.", "Calculates for the other babies with the same gestational age."),
+    ("Nascimento do Bebê {}:", "Birth of Baby {}:"),
+    ("mesma do Bebê 1", "same as Baby 1"),
+    ("Bebê {}: {}", "Baby {}: {}"),
+    ("Bebê {} (nascimento em {})", "Baby {} (born on {})"),
+    ("Bebê {}", "Baby {}"),
+    ("-30 dias", "-30 days"),
+    ("Preenche a data de referência a partir de hoje.", "Fills in the reference date counting back from today."),
+    ("Área de transferência não disponível (comum em sessões Wayland ou sem ambiente gráfico). Use a exportação em PDF ou selecione o texto.", "Clipboard not available (common in Wayland or headless sessions). Use the PDF export or select the text."),
+    ("{} O resultado foi copiado como texto simples.", "{} The result was copied as plain text."),
+    ("Falha ao salvar o cadastro: {}", "Failed to save the patient list: {}"),
+    ("Informe o nome do paciente.", "Enter the patient's name."),
+    ("Nome:", "Name:"),
+    ("Adicionar", "Add"),
+    ("Calcular e adicionar", "Calculate and add"),
+    ("Calcula as idades e salva o paciente no cadastro.", "Calculates the ages and saves the patient to the list."),
+    ("{} adicionado(a) ao cadastro.", "{} added to the patient list."),
+    ("Corrija os dados do formulário antes de salvar.", "Fix the form data before saving."),
+    ("Atualizar selecionado", "Update selected"),
+    ("Painel do Dia", "Today's Dashboard"),
+    ("Importar...", "Import..."),
+    ("{} paciente(s) importado(s).", "{} patient(s) imported."),
+    ("Falha ao importar: {}", "Failed to import: {}"),
+    ("Exportar...", "Export..."),
+    ("Falha ao exportar: {}", "Failed to export: {}"),
+    ("Exportar sem identificação (pesquisa)", "Export de-identified (research)"),
+    ("Omite nomes e datas; cada paciente recebe um código anônimo, o mesmo durante esta sessão.", "Omits names and dates; each patient gets an anonymous code, the same during this session."),
+    ("Nenhum paciente cadastrado.", "No patients saved."),
+    ("Excluir", "Delete"),
+    ("Ordenar por:", "Sort by:"),
+    ("Nome", "Name"),
+    ("Idade corrigida", "Corrected age"),
+    ("Gerar resumo semanal...", "Create weekly summary..."),
+    ("Resumo salvo em {}", "Summary saved to {}"),
+    ("Falha ao salvar o resumo: {}", "Failed to save the summary: {}"),
+    ("Paciente", "Patient"),
+    ("Idade Cronológica", "Chronological Age"),
+    ("Idade Corrigida", "Corrected Age"),
+    ("{} semanas ({} meses)", "{} weeks ({} months)"),
+    ("{} semanas e {} dias", "{} weeks and {} days"),
+    ("Nenhum cálculo nesta sessão.", "No calculations in this session."),
+    ("Limpar Histórico", "Clear History"),
+    ("Datas de referência (DD/MM/AAAA), uma por linha:", "Reference dates (DD/MM/YYYY), one per line:"),
+    ("Data inválida: \"{}\"", "Invalid date: \"{}\""),
+    ("{} é anterior ao nascimento.", "{} is before the birth."),
+    ("Tabela salva em {}", "Table saved to {}"),
+    ("Data", "Date"),
+    ("Falha ao salvar as configurações: {}", "Failed to save the settings: {}"),
+    ("Convenção para a idade em anos:", "Convention for age in years:"),
+    ("A contagem coreana considera 1 ano ao nascer e soma um ano a cada 1º de janeiro.", "Korean age counting starts at 1 year at birth and adds a year every January 1st."),
+    ("Mostrar as fórmulas de cálculo (modo didático)", "Show the calculation formulas (teaching mode)"),
+    ("No modo para os pais, arredondar os dias (\"8 semanas e meia\")", "In parent mode, round the days (\"8 and a half weeks\")"),
+    ("Tecla Enter nos campos:", "Enter key in the fields:"),
+    ("Ao entrar em um campo, manter o cursor no fim (sem selecionar o texto)", "When entering a field, keep the cursor at the end (don't select the text)"),
+    ("Aceitar semanas decimais (ex.: 32,5)", "Accept decimal weeks (e.g. 32.5)"),
+    ("A fração é convertida no número de dias mais próximo.", "The fraction is converted to the nearest number of days."),
+    ("Destacar a área de resultados ao calcular", "Highlight the results area when calculating"),
+    ("Tocar um som ao calcular", "Play a sound when calculating"),
+    ("Limpar o formulário após exportar", "Clear the form after exporting"),
+    ("Vazio, conta como 0 dias.", "Blank counts as 0 days."),
+    ("Próximas vacinas (calendário do PNI)", "Upcoming vaccines (Brazilian PNI schedule)"),
+    ("Nenhuma vacina prevista até os 15 meses.", "No vaccines scheduled up to 15 months."),
+    ("Ao nascer", "At birth"),
+    ("{}: {} (idade corrigida: {} semanas)", "{}: {} (corrected age: {} weeks)"),
+    (
+        "As vacinas seguem a idade cronológica, também nos prematuros. \
+         Confirme sempre com o calendário vigente e o pediatra.",
+        "Vaccines follow the chronological age, also for preterm babies. \
+         Always check the current schedule and the pediatrician.",
+    ),
+    ("BCG e Hepatite B", "BCG and Hepatitis B"),
+    ("A BCG é aplicada quando o bebê atinge 2 kg.", "BCG is given once the baby reaches 2 kg."),
+    (
+        "Pentavalente, VIP (poliomielite), Pneumocócica 10-valente e Rotavírus (1ª dose)",
+        "Pentavalent, IPV (polio), 10-valent pneumococcal and Rotavirus (1st dose)",
+    ),
+    (
+        "Rotavírus: 1ª dose até 3 meses e 15 dias de idade cronológica.",
+        "Rotavirus: 1st dose up to 3 months and 15 days of chronological age.",
+    ),
+    ("Meningocócica C (1ª dose)", "Meningococcal C (1st dose)"),
+    (
+        "Pentavalente, VIP (poliomielite), Pneumocócica 10-valente e Rotavírus (2ª dose)",
+        "Pentavalent, IPV (polio), 10-valent pneumococcal and Rotavirus (2nd dose)",
+    ),
+    (
+        "Rotavírus: 2ª dose até 7 meses e 29 dias de idade cronológica.",
+        "Rotavirus: 2nd dose up to 7 months and 29 days of chronological age.",
+    ),
+    ("Meningocócica C (2ª dose)", "Meningococcal C (2nd dose)"),
+    (
+        "Pentavalente e VIP (3ª dose), Influenza e Covid-19",
+        "Pentavalent and IPV (3rd dose), Influenza and Covid-19",
+    ),
+    ("Febre amarela", "Yellow fever"),
+    (
+        "Tríplice viral (1ª dose), Pneumocócica 10-valente e Meningocócica (reforço)",
+        "MMR (1st dose), 10-valent pneumococcal and Meningococcal (booster)",
+    ),
+    (
+        "DTP e VIP (1º reforço), Hepatite A e Tetraviral",
+        "DTP and IPV (1st booster), Hepatitis A and MMRV",
+    ),
+    ("Atalho: {}", "Shortcut: {}"),
+    ("Atalho: {} (fora dos campos de texto)", "Shortcut: {} (outside text fields)"),
+    (
+        "Bebê a termo: idade corrigida igual à cronológica.",
+        "Term baby: corrected age equals chronological age.",
+    ),
+    ("Idades em Semanas Decimais", "Ages in Decimal Weeks"),
+    ("cronológica {}; corrigida {}", "chronological {}; corrected {}"),
+    (
+        "dias ÷ 7, com uma casa decimal = {} ÷ 7 e {} ÷ 7",
+        "days ÷ 7, to one decimal place = {} ÷ 7 and {} ÷ 7",
+    ),
+    ("Idade corrigida hoje: {} semanas e {} dias", "Corrected age today: {} weeks and {} days"),
+    (
+        "Com a janela minimizada, mostrar a idade corrigida no título",
+        "When minimized, show the corrected age in the window title",
+    ),
+    (
+        "O título aparece ao passar o mouse sobre o ícone na barra de tarefas e é \
+         atualizado a cada dia.",
+        "The title shows when hovering the taskbar icon and is updated every day.",
+    ),
+    ("Exportar o PDF em preto e branco", "Export the PDF in black and white"),
+    (
+        "Para impressoras monocromáticas: os destaques ficam em negrito com um marcador, em vez de coloridos.",
+        "For monochrome printers: highlights are bold with a marker instead of colored.",
+    ),
+    ("Para digitar vários bebês em sequência. A limpeza pode ser desfeita.", "For entering several babies in a row. Clearing can be undone."),
+    ("Marcar as consultas em dias de atendimento", "Schedule appointments on clinic days"),
+    ("Move cada data para o dia de atendimento mais próximo.", "Moves each date to the nearest clinic day."),
+    ("Configurações importadas.", "Settings imported."),
+    ("Configurações importadas. Campos inválidos ignorados: {}.", "Settings imported. Invalid fields ignored: {}."),
+    ("Configurações exportadas.", "Settings exported."),
+    ("Desenvolvido em Rust por Paulo A V Munhoz", "Developed in Rust by Paulo A V Munhoz"),
+    ("Opções", "Options"),
+    ("Configurações...", "Settings..."),
+    ("Comparar datas de referência...", "Compare reference dates..."),
+    ("Histórico de cálculos...", "Calculation history..."),
+    ("Exibição", "View"),
+    ("Alterne com Ctrl+M", "Switch with Ctrl+M"),
+    ("Configurações", "Settings"),
+    ("Pacientes", "Patients"),
+    ("Comparar Datas", "Compare Dates"),
+    ("Histórico", "History"),
+    ("Data de Nascimento (DD/MM/AAAA):", "Date of Birth (DD/MM/YYYY):"),
+    ("Idade Gestacional (semanas):", "Gestational Age (weeks):"),
+    ("Com datação incerta, informe um intervalo (ex.: 31-33).", "If dating is uncertain, enter a range (e.g. 31-33)."),
+    ("Dias na Semana de Nascimento:", "Days into the Birth Week:"),
+    ("DUM (opcional):", "LMP (optional):"),
+    ("Data da última menstruação, usada para conferir a idade gestacional ou, conforme as configurações, como fonte dela.", "Last menstrual period, used to check the gestational age or, depending on the settings, as its source."),
+    ("Observador/fonte (opcional):", "Observer/source (optional):"),
+    ("Quem mediu ou de onde vem a idade gestacional.", "Who measured the gestational age or where it comes from."),
+    ("Data de Referência (opcional):", "Reference Date (optional):"),
+    ("hoje", "today"),
+    ("Data em que as idades são calculadas, por exemplo a de uma consulta passada. Vazia, usa a data de hoje.", "Date on which the ages are calculated, for example that of a past appointment. If empty, today's date is used."),
+    ("Bebê a termo (40 semanas)", "Term baby (40 weeks)"),
+    ("Preenche 40 semanas e 0 dias e recalcula.", "Fills in 40 weeks and 0 days and recalculates."),
+    ("Normalizar", "Normalize"),
+    ("Remove espaços, completa as datas e separa idades gestacionais como \"32+4\".", "Removes spaces, completes the dates and splits gestational ages such as \"32+4\"."),
+    ("{}: dia e mês invertidos? Você quis dizer {}?", "{}: day and month swapped? Did you mean {}?"),
+    ("Usar esta data", "Use this date"),
+    ("Calcular", "Calculate"),
+    ("Limpar", "Clear"),
+    ("Desfazer", "Undo"),
+    ("Idades calculadas em {}.", "Ages calculated on {}."),
+    ("Classificação: {}", "Classification: {}"),
+    ("hoje!", "today!"),
+    ("em {} (faltam {} dias)", "on {} ({} days to go)"),
+    ("Próximo mesversário corrigido: {} meses {}", "Next corrected monthly birthday: {} months {}"),
+    ("Faltam {} semanas para a correção deixar de ser aplicada ({} meses corrigidos).", "{} weeks left until the correction no longer applies ({} corrected months)."),
+    ("Detalhes do cálculo", "Calculation details"),
+    ("Copiar este valor", "Copy this value"),
+    ("E se tivesse nascido a termo?", "What if born at term?"),
+    ("Um bebê a termo não precisa de correção: a idade corrigida seria igual à cronológica. A diferença é o tempo que faltou de gestação.", "A term baby needs no correction: the corrected age would equal the chronological age. The difference is the gestation time that was missing."),
+    ("Consultas de seguimento (idade corrigida)", "Follow-up appointments (corrected age)"),
+    ("{} meses corrigidos", "{} corrected months"),
+    ("Marcos do desenvolvimento esperados", "Expected developmental milestones"),
+    ("Motor: {}", "Motor: {}"),
+    ("Linguagem: {}", "Language: {}"),
+    ("Referência geral: cada criança tem seu ritmo. Converse com o pediatra em caso de dúvida.", "General reference: every child has their own pace. Talk to the pediatrician if in doubt."),
+    ("Copiar todas as datas", "Copy all dates"),
+    ("Nascimento, termo, concepção e consultas, uma por linha.", "Birth, term, conception and appointments, one per line."),
+    ("Copiar Resultado", "Copy Result"),
+    ("Copiar como HTML", "Copy as HTML"),
+    ("Copiar para SMS", "Copy for SMS"),
+    ("Resumo de até 160 caracteres", "Summary of up to 160 characters"),
+    ("Resumo semanal de idades ({} a {})\n{} paciente(s)\n", "Weekly age summary ({} to {})\n{} patient(s)\n"),
+    ("  Nascimento: {} ({} semanas e {} dias de gestação)\n  Idade Cronológica: {} semanas ({} meses)\n  Idade Corrigida: {} semanas e {} dias\n", "  Birth: {} ({} weeks and {} days of gestation)\n  Chronological Age: {} weeks ({} months)\n  Corrected Age: {} weeks and {} days\n"),
+    ("  Próximo mesversário corrigido: {} meses em {}\n", "  Next corrected monthly birthday: {} months on {}\n"),
+    ("{} semanas ({} meses) e {} dias", "{} weeks ({} months) and {} days"),
+    ("Idade Corrigida (meses e dias)", "Corrected Age (months and days)"),
+    ("Idade Corrigida (intervalo de IG)", "Corrected Age (GA range)"),
+    ("{}–{} semanas", "{}–{} weeks"),
+    ("Idade Pós-Menstrual", "Postmenstrual Age"),
+    ("Data Provável do Parto (DPP)", "Estimated Due Date (EDD)"),
+    ("{} (no dia do nascimento)", "{} (on the day of birth)"),
+    ("{} (antes do nascimento)", "{} (before birth)"),
+    ("Idade Corrigida (Anos)", "Corrected Age (Years)"),
+    ("{} anos, {} meses e {} dias", "{} years, {} months and {} days"),
+    ("Idade Corrigida (Anos, {})", "Corrected Age (Years, {})"),
+    ("{} anos", "{} years"),
+    (
+        "idade cronológica = dias desde o nascimento ÷ 7 = {} ÷ 7; meses: {}",
+        "chronological age = days since birth ÷ 7 = {} ÷ 7; months: {}",
+    ),
+    ("Meses totais (\"12 semanas (2 meses)\"):", "Total months (\"12 weeks (2 months)\"):"),
+    ("Média de 30,4375 dias por mês", "Average of 30.4375 days per month"),
+    ("Meses de calendário completos", "Whole calendar months"),
+    (
+        "Os meses de calendário coincidem com a idade em anos, meses e dias; a média \
+         pode dar um mês a menos perto do \"mesversário\".",
+        "Calendar months match the age in years, months and days; the average may \
+         give one month less around the monthly birthday.",
+    ),
+    ("idade corrigida = idade cronológica − prematuridade = {} − {} = {} dias", "corrected age = chronological age − prematurity = {} − {} = {} days"),
+    ("meses de calendário completos desde a data do termo ({}) e dias restantes", "complete calendar months since the term date ({}) and remaining days"),
+    ("idade corrigida calculada com o início e com o fim do intervalo de IG", "corrected age calculated with the start and the end of the GA range"),
+    ("idade gestacional ao nascer + dias de vida = {} + {} dias", "gestational age at birth + days of life = {} + {} days"),
+    ("DPP = nascimento + (280 − dias de gestação) = {} + ({} − {}) dias", "EDD = birth + (280 − days of gestation) = {} + ({} − {}) days"),
+    ("anos, meses e dias de calendário desde a data do termo ({})", "calendar years, months and days since the term date ({})"),
+    ("ano atual − ano da data do termo + 1 = {} − {} + 1", "current year − year of the term date + 1 = {} − {} + 1"),
+    ("dias desde o nascimento + 1 (o nascimento é o 1º dia)", "days since birth + 1 (the birth is day 1)"),
+    ("nascimento − (idade gestacional − 14 dias)", "birth − (gestational age − 14 days)"),
+    ("{} semanas − idade gestacional = {} − {} dias", "{} weeks − gestational age = {} − {} days"),
+    ("nascimento + dias de prematuridade", "birth + days of prematurity"),
+    ("informado no formulário; não entra no cálculo", "entered in the form; not used in the calculation"),
+    ("Desde o nascimento (cronológica): {} semanas e {} dias | {} meses e {} dias", "Since birth (chronological): {} weeks and {} days | {} months and {} days"),
+    ("Desde o termo (corrigida): {} semanas e {} dias | {}", "Since term (corrected): {} weeks and {} days | {}"),
+    ("Pós-menstrual (IG ao nascer + vida): {} semanas e {} dias", "Postmenstrual (GA at birth + life): {} weeks and {} days"),
+    ("Seu bebê tem {} semanas de vida (cerca de {} meses).", "Your baby is {} weeks old (about {} months)."),
+    ("Como a data da gestação é incerta, o desenvolvimento dele deve ser comparado ao de um bebê de {} a {} semanas (idade corrigida).", "Since the pregnancy dating is uncertain, your baby's development should be compared to that of a baby {} to {} weeks old (corrected age)."),
+    ("Como nasceu antes do tempo, o desenvolvimento dele deve ser comparado ao de um bebê de {} (idade corrigida).", "Since your baby was born early, their development should be compared to that of a baby {} old (corrected age)."),
+    ("Como nasceu a termo, não é preciso corrigir a idade.", "Since your baby was born at term, there is no need to correct the age."),
+    ("A data prevista para o parto era {}.", "The due date was {}."),
+    ("1 semana", "1 week"),
+    ("{} semanas", "{} weeks"),
+    ("{} e um terço", "{} and a third"),
+    ("{} e meia", "{} and a half"),
+    ("{} e dois terços", "{} and two thirds"),
+    ("{} e 1 dia", "{} and 1 day"),
+    ("{} e {} dias", "{} and {} days"),
+    ("Calculado em {}", "Calculated on {}"),
+    ("Data de nascimento: {}", "Date of birth: {}"),
+    ("Idade gestacional ao nascer: {} semanas e {} dias", "Gestational age at birth: {} weeks and {} days"),
+    ("Idade corrigida se nascesse a termo", "Corrected age if born at term"),
+    ("Idade corrigida real", "Actual corrected age"),
+    ("Diferença", "Difference"),
+    ("{} dias ({} semanas e {} dias)", "{} days ({} weeks and {} days)"),
+    ("Dia de Vida", "Day of Life"),
+    ("{}º dia", "day {}"),
+    ("Data Estimada da Concepção", "Estimated Conception Date"),
+    ("Dias de Prematuridade", "Days of Prematurity"),
+    ("{} dias", "{} days"),
+    ("Data do Termo ({} semanas)", "Term Date ({} weeks)"),
+    ("Observador/Fonte", "Observer/Source"),
+    ("{} corrigidos", "{} corrected"),
+    ("{} {} e {} {}", "{} {} and {} {}"),
+    ("Id.cron {}sem ({}m); Id.corr {}s{}d ({}a{}m{}d)", "Chron {}wk ({}mo); Corr {}w{}d ({}y{}m{}d)"),
+    ("IPM {}s{}d", "PMA {}w{}d"),
+    ("Consulta {}m corr: {}", "Visit {}m corr: {}"),
+    ("{} meses", "{} months"),
+    ("Data de nascimento", "Date of birth"),
+    ("Data estimada da concepção", "Estimated conception date"),
+    ("Data do termo ({} semanas / idade corrigida zero)", "Term date ({} weeks / corrected age zero)"),
+    ("Próximo mês completo ({} meses corrigidos)", "Next full month ({} corrected months)"),
+    ("Consulta de {} meses corrigidos", "{} corrected months appointment"),
+    ("Intervalo de idade gestacional inválido. Use, por exemplo, 31-33.", "Invalid gestational age range. Use, for example, 31-33."),
+    ("O fim do intervalo de idade gestacional deve ser maior que o início.", "The end of the gestational age range must be greater than the start."),
+    ("Idade gestacional com casas decimais não é aceita. Informe semanas e dias em campos separados, ou ative as semanas decimais em Opções > Configurações.", "Gestational age with decimals is not accepted. Enter weeks and days in separate fields, or enable decimal weeks in Options > Settings."),
+    ("Idade gestacional deve ser um número.", "Gestational age must be a number."),
+    ("Idade gestacional mínima suportada é de {} semanas.", "The minimum supported gestational age is {} weeks."),
+    ("Idade gestacional máxima suportada é de {} semanas. Confira o valor digitado.", "The maximum supported gestational age is {} weeks. Check the value entered."),
+    ("Dias na semana de nascimento devem ser um número.", "Days into the birth week must be a number."),
+    ("Dias devem estar entre 0 e 6.", "Days must be between 0 and 6."),
+    ("nascido em {} com {}, em {}", "born on {} at {}, on {}"),
+    ("Cronológica: {} semanas; corrigida: {} semanas e {} dias", "Chronological: {} weeks; corrected: {} weeks and {} days"),
+    ("Data de nascimento não pode ser futura.", "Date of birth cannot be in the future."),
+    ("A data de referência está a mais de {} anos do nascimento. Confira o ano digitado.", "The reference date is more than {} years after the birth. Check the year entered."),
+    ("Desfazer a limpeza do formulário", "Undo clearing the form"),
+    ("Desfazer a exclusão de {}", "Undo deleting {}"),
+    ("Texto", "Text"),
+    ("FHIR simplificado (JSON)", "Simplified FHIR (JSON)"),
+    ("Planilha (CSV)", "Spreadsheet (CSV)"),
+    ("Prematuro extremo (< 28 semanas)", "Extremely preterm (< 28 weeks)"),
+    ("Muito prematuro (28 a 31 semanas)", "Very preterm (28 to 31 weeks)"),
+    ("Prematuro moderado a tardio (32 a 36 semanas)", "Moderate to late preterm (32 to 36 weeks)"),
+    ("A termo (37 semanas ou mais)", "Term (37 weeks or more)"),
+    ("Internacional", "International"),
+    ("Contagem coreana", "Korean age"),
+    ("Modo clínico", "Clinician mode"),
+    ("Modo para os pais", "Parent mode"),
+    ("Modo completo", "Complete mode"),
+    ("Seguir o sistema", "Follow the system"),
+    ("Claro", "Light"),
+    ("Escuro", "Dark"),
+    ("Avança para o próximo campo", "Moves to the next field"),
+    ("Calcula, se os dados estiverem completos", "Calculates, if the data is complete"),
+    ("Seg", "Mon"),
+    ("Ter", "Tue"),
+    ("Qua", "Wed"),
+    ("Qui", "Thu"),
+    ("Sex", "Fri"),
+    ("Sáb", "Sat"),
+    ("Dom", "Sun"),
+    ("Janeiro", "January"),
+    ("Fevereiro", "February"),
+    ("Março", "March"),
+    ("Abril", "April"),
+    ("Maio", "May"),
+    ("Junho", "June"),
+    ("Julho", "July"),
+    ("Agosto", "August"),
+    ("Setembro", "September"),
+    ("Outubro", "October"),
+    ("Novembro", "November"),
+    ("Dezembro", "December"),
+    ("Semanas", "Weeks"),
+    ("Dias", "Days"),
+    ("DUM", "LMP"),
+    ("Data de referência", "Reference date"),
+    ("texto", "text"),
+    ("mês", "month"),
+    ("meses", "months"),
+    ("dia", "day"),
+    ("dias", "days"),
+    ("Sustenta a cabeça sem apoio; leva as mãos à boca.", "Holds head up without support; brings hands to mouth."),
+    ("Ri e balbucia; vira a cabeça na direção de sons.", "Laughs and babbles; turns head toward sounds."),
+    ("Senta sem apoio; passa objetos de uma mão para a outra.", "Sits without support; passes objects from one hand to the other."),
+    ("Repete sílabas (\"mamama\", \"papapa\"); reage ao próprio nome.", "Repeats syllables (\"mamama\", \"papapa\"); responds to own name."),
+    ("Fica em pé com apoio e dá passos segurando-se nos móveis.", "Stands with support and takes steps holding on to furniture."),
+    ("Fala uma ou duas palavras com significado; entende pedidos simples.", "Says one or two words with meaning; understands simple requests."),
+    ("Anda sozinho; sobe degraus com ajuda.", "Walks alone; climbs steps with help."),
+    ("Fala várias palavras; aponta para mostrar o que quer.", "Says several words; points to show what they want."),
+    ("Corre; chuta uma bola.", "Runs; kicks a ball."),
+    ("Junta duas palavras em frases curtas; nomeia objetos e figuras.", "Puts two words together in short phrases; names objects and pictures."),
+];
+
+/// Textos em espanhol, pelo texto em português.
+const SPANISH: &[(&str, &str)] = &[
+    ("Escolher no calendário", "Elegir en el calendario"),
+    ("{} de {}", "{} de {}"),
+    ("Hoje", "Hoy"),
+    (
+        "Calculadora de Idade Gestacional do Bebê",
+        "Calculadora de Edad Gestacional del Bebé",
+    ),
+    (
+        "Formato da data de referência inválido. Use DD/MM/AAAA.",
+        "Formato de fecha de referencia inválido. Use DD/MM/AAAA.",
+    ),
+    (
+        "Formato de data inválido. Use DD/MM/AAAA.",
+        "Formato de fecha inválido. Use DD/MM/AAAA.",
+    ),
+    (
+        "Formato da DUM inválido. Use DD/MM/AAAA.",
+        "Formato de FUM inválido. Use DD/MM/AAAA.",
+    ),
+    (
+        "A DUM não pode ser posterior à data de nascimento.",
+        "La FUM no puede ser posterior a la fecha de nacimiento.",
+    ),
+    (
+        "Área de transferência não disponível.",
+        "Portapapeles no disponible.",
+    ),
+    ("Importar...", "Importar..."),
+    ("Exportar...", "Exportar..."),
+    ("Idade Cronológica", "Edad Cronológica"),
+    ("Idade Corrigida", "Edad Corregida"),
+    ("{} semanas ({} meses)", "{} semanas ({} meses)"),
+    ("{} semanas e {} dias", "{} semanas y {} días"),
+    (
+        "Desenvolvido em Rust por Paulo A V Munhoz",
+        "Desarrollado en Rust por Paulo A V Munhoz",
+    ),
+    ("Opções", "Opciones"),
+    ("Configurações...", "Configuración..."),
+    ("Exibição", "Vista"),
+    ("Configurações", "Configuración"),
+    ("Pacientes", "Pacientes"),
+    ("Histórico", "Historial"),
+    (
+        "Data de Nascimento (DD/MM/AAAA):",
+        "Fecha de Nacimiento (DD/MM/AAAA):",
+    ),
+    (
+        "Idade Gestacional (semanas):",
+        "Edad Gestacional (semanas):",
+    ),
+    (
+        "Dias na Semana de Nascimento:",
+        "Días en la Semana de Nacimiento:",
+    ),
+    ("DUM (opcional):", "FUM (opcional):"),
+    (
+        "Observador/fonte (opcional):",
+        "Observador/fuente (opcional):",
+    ),
+    (
+        "Data de Referência (opcional):",
+        "Fecha de Referencia (opcional):",
+    ),
+    ("hoje", "hoy"),
+    ("Bebê a termo (40 semanas)", "Bebé a término (40 semanas)"),
+    ("Normalizar", "Normalizar"),
+    ("Usar esta data", "Usar esta fecha"),
+    ("Calcular", "Calcular"),
+    ("Limpar", "Limpiar"),
+    ("Desfazer", "Deshacer"),
+    ("Detalhes do cálculo", "Detalles del cálculo"),
+    ("Copiar Resultado", "Copiar Resultado"),
+    ("Copiar como HTML", "Copiar como HTML"),
+    ("Copiar para SMS", "Copiar para SMS"),
+    (
+        "{} semanas ({} meses) e {} dias",
+        "{} semanas ({} meses) y {} días",
+    ),
+    ("Idade Pós-Menstrual", "Edad Posmenstrual"),
+    (
+        "Data Provável do Parto (DPP)",
+        "Fecha Probable de Parto (FPP)",
+    ),
+    (
+        "Idade gestacional deve ser um número.",
+        "La edad gestacional debe ser un número.",
+    ),
+    (
+        "Idade gestacional mínima suportada é de {} semanas.",
+        "La edad gestacional mínima admitida es de {} semanas.",
+    ),
+    (
+        "Idade gestacional máxima suportada é de {} semanas. Confira o valor digitado.",
+        "La edad gestacional máxima admitida es de {} semanas. Revise el valor ingresado.",
+    ),
+    (
+        "Dias na semana de nascimento devem ser um número.",
+        "Los días de la semana de nacimiento deben ser un número.",
+    ),
+    (
+        "Dias devem estar entre 0 e 6.",
+        "Los días deben estar entre 0 y 6.",
+    ),
+    (
+        "Data de nascimento não pode ser futura.",
+        "La fecha de nacimiento no puede ser futura.",
+    ),
+    ("Modo clínico", "Modo clínico"),
+    ("Modo para os pais", "Modo para los padres"),
+    ("Modo completo", "Modo completo"),
+    ("Seguir o sistema", "Seguir el sistema"),
+    ("Claro", "Claro"),
+    ("Escuro", "Oscuro"),
+    ("Janeiro", "Enero"),
+    ("Fevereiro", "Febrero"),
+    ("Março", "Marzo"),
+    ("Abril", "Abril"),
+    ("Maio", "Mayo"),
+    ("Junho", "Junio"),
+    ("Julho", "Julio"),
+    ("Agosto", "Agosto"),
+    ("Setembro", "Septiembre"),
+    ("Outubro", "Octubre"),
+    ("Novembro", "Noviembre"),
+    ("Dezembro", "Diciembre"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn portuguese_is_the_key_itself() {
+        assert_eq!(t("Calcular", Lang::PtBr), "Calcular");
+        assert_eq!(
+            tf("{} semanas e {} dias", Lang::PtBr, &[&11, &2]),
+            "11 semanas e 2 dias"
+        );
+    }
+
+    #[test]
+    fn english_translation_is_filled_in_order() {
+        assert_eq!(t("Calcular", Lang::En), "Calculate");
+        assert_eq!(
+            tf("{} semanas e {} dias", Lang::En, &[&11, &2]),
+            "11 weeks and 2 days"
+        );
+    }
+
+    #[test]
+    fn missing_translation_falls_back_to_portuguese() {
+        assert_eq!(t("Texto que não existe", Lang::En), "Texto que não existe");
+    }
+
+    #[test]
+    fn translations_keep_the_placeholders() {
+        for (pt, translated) in ENGLISH.iter().chain(SPANISH) {
+            assert_eq!(
+                pt.matches("{}").count(),
+                translated.matches("{}").count(),
+                "{}",
+                pt
+            );
+        }
+    }
+}
@@ -0,0 +1,50 @@
+// Últimos valores digitados no formulário, gravados ao fechar o programa e
+// restaurados ao abri-lo, para quem acompanha sempre o mesmo bebê.
+use crate::growth::Sex;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::io;
+
+/// Nome do arquivo dos últimos valores no diretório de configuração.
+const LAST_INPUTS_FILE: &str = "ultimos_valores.json";
+
+/// Campos do formulário guardados entre as sessões. Campos ausentes no arquivo
+/// ficam vazios; assim, arquivos de versões anteriores, sem os outros bebês, são
+/// lidos como de um único bebê.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LastInputs {
+    pub birth_date: String,
+    pub gestational_weeks: String,
+    pub gestational_days: String,
+    pub sex: Option<Sex>,
+    /// Nascimento do Bebê 2 e do Bebê 3 de uma gestação múltipla, como digitados
+    /// (vazio é o mesmo do Bebê 1). A lista vazia indica um único bebê.
+    pub sibling_birth_dates: Vec<String>,
+}
+
+impl LastInputs {
+    /// Carrega os últimos valores, ou campos vazios se não houver arquivo válido.
+    pub fn load() -> Self {
+        storage::load(LAST_INPUTS_FILE)
+    }
+
+    /// Grava os valores no diretório de configuração.
+    pub fn save(&self) -> io::Result<()> {
+        storage::save(LAST_INPUTS_FILE, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_without_siblings_are_a_single_baby() {
+        let json = r#"{"birth_date":"01/01/2024","gestational_weeks":"32","gestational_days":"4","sex":"Female"}"#;
+        let inputs: LastInputs = serde_json::from_str(json).unwrap();
+        assert_eq!(inputs.birth_date, "01/01/2024");
+        assert_eq!(inputs.sex, Some(Sex::Female));
+        assert!(inputs.sibling_birth_dates.is_empty());
+    }
+}
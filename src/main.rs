@@ -1,31 +1,354 @@
-// Oculta a janela do console no Windows.
-#![windows_subsystem = "windows"]
+// Oculta a janela do console no Windows, nas versões de lançamento. No modo de linha
+// de comando, o programa se conecta ao console de onde foi chamado (veja
+// 'cli::attach_parent_console').
+#![cfg_attr(all(windows, not(debug_assertions)), windows_subsystem = "windows")]
 
 // Importa as bibliotecas necessárias.
 // 'chrono' para manipulação de datas.
 // 'eframe' e 'egui' para a interface gráfica.
 // 'std::str::FromStr' para converter strings em números.
-use chrono::{Datelike, NaiveDate, Utc};
+use age::{
+    calculate_chronological_age, calculate_corrected_age, calculate_corrected_birthdate,
+    calculate_due_date, calculate_post_menstrual_days, calculate_prematurity_days,
+    date_at_corrected_months, ChronologicalAge, CorrectedAge, FULL_TERM_WEEKS,
+};
+use chrono::{Datelike, Months, NaiveDate, Utc};
+use clipboard::{clipboard_error_message, set_text_checked, ClipboardOp};
 use eframe::egui;
+use feedback::Cue;
+use i18n::{t, tf, Lang};
+use last_inputs::LastInputs;
+use roster::{DeidentifiedRecord, Patient, Roster};
+use settings::{
+    AgeConvention, ClinicDays, CorrectionTerm, DisplayMode, EnterBehavior, GestationalAgePriority,
+    GestationalAgeSource, MonthCount, Settings, Theme, UiScale,
+};
 use std::str::FromStr;
+use undo::{FormSnapshot, UndoEntry, UndoStack};
 
-/// Armazena a idade cronológica calculada.
-struct ChronologicalAge {
-    years: i32,
-    months: i32,
-    days: i32,
-    total_weeks: i64,
-    total_months: i64,
+mod age;
+#[cfg(not(target_arch = "wasm32"))]
+mod cli;
+mod clipboard;
+mod date_picker;
+mod dates;
+mod diagnostics;
+mod feedback;
+mod feeding;
+mod fhir;
+mod file_dialog;
+mod growth;
+mod i18n;
+mod last_inputs;
+mod milestones;
+mod pdf;
+mod roster;
+mod settings;
+mod storage;
+mod tray;
+mod undo;
+mod vaccines;
+
+/// Menor idade gestacional aceita, em semanas completas.
+const MIN_GESTATIONAL_WEEKS: i32 = 22;
+/// Maior idade gestacional aceita, em semanas completas. Gestações mais longas
+/// são praticamente impossíveis e indicam erro de digitação.
+const MAX_GESTATIONAL_WEEKS: i32 = 45;
+/// Abaixo desta idade gestacional (em semanas) o bebê está no limite da viabilidade
+/// e o resultado é acompanhado de um aviso.
+const PERIVIABLE_WEEKS: i32 = 24;
+
+/// Diferença máxima, em dias, aceita entre a idade gestacional informada e a
+/// calculada pela data da última menstruação (DUM) antes de exibir um aviso.
+const LMP_TOLERANCE_DAYS: i64 = 14;
+
+/// Maior distância plausível, em anos, entre o nascimento e a data de referência.
+/// Além dela, a data quase certamente tem o ano digitado errado.
+const MAX_AGE_YEARS: u32 = 150;
+
+/// Atalhos dos botões "Copiar Resultado" e "Limpar".
+const COPY_SHORTCUT: egui::KeyboardShortcut =
+    egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::C);
+const CLEAR_SHORTCUT: egui::KeyboardShortcut =
+    egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::L);
+
+/// Intervalo entre as verificações de mudança de data com a janela ociosa.
+const REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Tamanho máximo de uma mensagem SMS simples, em caracteres.
+const SMS_MAX_CHARS: usize = 160;
+
+/// Idades corrigidas (em meses) das consultas de seguimento padrão do prematuro.
+const FOLLOW_UP_CHECKPOINTS_MONTHS: [u32; 5] = [4, 8, 12, 18, 24];
+
+/// Idade corrigida, em meses, até a qual a correção pela prematuridade é aplicada;
+/// depois dela, o desenvolvimento é avaliado pela idade cronológica.
+const CORRECTION_CUTOFF_MONTHS: u32 = 24;
+
+/// Com quantas semanas de antecedência o fim da correção passa a ser anunciado.
+const CUTOFF_NOTICE_WEEKS: i64 = 12;
+
+/// Faixa de pesos aceita no campo de peso, em gramas.
+const MIN_WEIGHT_GRAMS: f64 = 200.0;
+const MAX_WEIGHT_GRAMS: f64 = 15000.0;
+
+/// Quantos cálculos o histórico da sessão guarda; os mais antigos são descartados.
+const HISTORY_MAX_ENTRIES: usize = 50;
+
+/// Explicações dos campos para quem não é da área da saúde, nas dicas do formulário.
+/// As que citam o termo recebem as semanas do termo da correção configurado.
+const WEEKS_HELP: &str =
+    "Semanas completas de gestação ao nascer, conforme o obstetra ({} é o termo).";
+const DAYS_HELP: &str = "Dias além das semanas completas, de 0 a 6 (ex.: 32 semanas e 4 dias).";
+/// Explicação da idade corrigida, na dica do resultado.
+const CORRECTED_AGE_HELP: &str = "Idade corrigida: a idade descontando as semanas que faltaram \
+     para as {} semanas de gestação, usada para avaliar o desenvolvimento do prematuro.";
+
+/// Atalhos da data de referência: rótulo do botão e quantos dias antes de hoje.
+const REFERENCE_DATE_SHORTCUTS: [(&str, i64); 3] = [("Hoje", 0), ("-7 dias", 7), ("-30 dias", 30)];
+
+/// Consulta de seguimento: data em que o bebê atinge uma idade corrigida padrão.
+struct Checkpoint {
+    corrected_months: u32,
+    date: NaiveDate,
+}
+
+/// Conjunto de todas as idades e datas calculadas para um bebê em uma data.
+struct Report {
+    today: NaiveDate,
+    birthdate: NaiveDate,
+    corrected_birthdate: NaiveDate,
+    /// Data provável do parto (40 semanas), mesmo que anterior ao nascimento.
+    due_date: NaiveDate,
+    chronological: ChronologicalAge,
+    corrected: CorrectedAge,
+    /// Dias que faltaram para completar o termo da correção (0 para bebês a termo).
+    prematurity_days: i64,
+    /// Idade pós-menstrual em dias: idade gestacional ao nascer + dias de vida.
+    post_menstrual_days: i64,
+    /// Dia de vida, contando o dia do nascimento como o 1º dia.
+    day_of_life: i64,
+    /// Data estimada da concepção, cerca de duas semanas após a última menstruação.
+    conception_date: NaiveDate,
+    checkpoints: Vec<Checkpoint>,
+    next_month_birthday: Option<Checkpoint>,
+    /// Idades corrigidas nos dois extremos, quando a idade gestacional foi
+    /// informada como intervalo (datação incerta).
+    corrected_range: Option<(CorrectedAge, CorrectedAge)>,
+    /// Observador ou fonte dos dados, apenas para rastreabilidade nos registros.
+    observer: Option<String>,
+    /// Fonte da idade gestacional usada, quando havia mais de uma (com a DUM).
+    gestational_age_source: Option<GestationalAgeSource>,
+    /// Como foram contados os meses totais das idades.
+    month_count: MonthCount,
+    /// Idade gestacional, em semanas, usada como termo na idade corrigida.
+    term_weeks: i32,
+    /// Peso na data do cálculo, em gramas, e o sexo, quando informados.
+    weight_grams: Option<f64>,
+    sex: Option<growth::Sex>,
+}
+
+/// Cálculo guardado no histórico da sessão: quando foi feito, com quais dados e
+/// o resultado principal.
+struct HistoryEntry {
+    time: String,
+    inputs: String,
+    result: String,
+}
+
+/// Critério de ordenação do painel de pacientes.
+#[derive(PartialEq)]
+enum DashboardSort {
+    Name,
+    CorrectedAge,
+}
+
+/// Formato do arquivo gerado por "Exportar..." na área de resultados.
+#[derive(Clone, Copy, PartialEq)]
+enum ExportFormat {
+    /// O mesmo texto copiado por "Copiar Resultado", com um bloco por bebê.
+    Text,
+    /// Observações no formato simplificado documentado em 'fhir.rs', um conjunto
+    /// por bebê.
+    Fhir,
+    /// Documento para imprimir ou anexar ao prontuário, gerado por 'pdf.rs', com
+    /// uma seção por bebê.
+    Pdf,
+    /// Uma linha de planilha por bebê, acrescentada ao arquivo se ele já existir,
+    /// para acompanhar o bebê consulta a consulta.
+    Csv,
+}
+
+impl ExportFormat {
+    /// Nome do formato exibido na interface.
+    fn label(self) -> &'static str {
+        match self {
+            ExportFormat::Text => "Texto",
+            ExportFormat::Fhir => "FHIR simplificado (JSON)",
+            ExportFormat::Pdf => "PDF",
+            ExportFormat::Csv => "Planilha (CSV)",
+        }
+    }
+
+    /// Nome sugerido para o arquivo.
+    fn file_name(self) -> &'static str {
+        match self {
+            ExportFormat::Text => "resultado.txt",
+            ExportFormat::Fhir => "resultado_fhir.json",
+            ExportFormat::Pdf => "resultado.pdf",
+            ExportFormat::Csv => "acompanhamento.csv",
+        }
+    }
+}
+
+/// Classificação do nascimento pela idade gestacional (categorias da OMS).
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PretermCategory {
+    /// Menos de 28 semanas.
+    Extreme,
+    /// De 28 a 31 semanas e 6 dias.
+    Very,
+    /// De 32 a 36 semanas e 6 dias.
+    ModerateToLate,
+    /// 37 semanas ou mais.
+    Term,
+}
+
+impl PretermCategory {
+    /// Nome da categoria exibido na interface, com a faixa de idade gestacional.
+    fn label(self) -> &'static str {
+        match self {
+            PretermCategory::Extreme => "Prematuro extremo (< 28 semanas)",
+            PretermCategory::Very => "Muito prematuro (28 a 31 semanas)",
+            PretermCategory::ModerateToLate => "Prematuro moderado a tardio (32 a 36 semanas)",
+            PretermCategory::Term => "A termo (37 semanas ou mais)",
+        }
+    }
+
+    /// Cor da categoria, do vermelho ao verde. No tema escuro, usa tons mais claros
+    /// para manter o contraste com o fundo.
+    fn color(self, dark_mode: bool) -> egui::Color32 {
+        let (light, dark) = match self {
+            PretermCategory::Extreme => ((180, 20, 20), (255, 110, 110)),
+            PretermCategory::Very => ((190, 80, 0), (255, 160, 80)),
+            PretermCategory::ModerateToLate => ((140, 110, 0), (235, 205, 70)),
+            PretermCategory::Term => ((20, 120, 40), (110, 215, 120)),
+        };
+        let (r, g, b) = if dark_mode { dark } else { light };
+        egui::Color32::from_rgb(r, g, b)
+    }
+}
+
+/// Entradas do formulário já validadas e convertidas.
+struct Inputs {
+    birthdate: NaiveDate,
+    gestational_weeks: i32,
+    /// Fim do intervalo de semanas, quando a datação é incerta (ex.: "31-33").
+    gestational_weeks_max: Option<i32>,
+    gestational_days: i32,
+    /// Fonte de onde vieram as semanas e os dias acima.
+    gestational_age_source: GestationalAgeSource,
+    last_menstrual_period: Option<NaiveDate>,
+    /// Quem mediu ou de onde veio a idade gestacional (ex.: "USG 1º trimestre").
+    observer: Option<String>,
+    /// Data em que as idades são calculadas, para revisões retroativas; sem ela,
+    /// usa a data atual.
+    reference_date: Option<NaiveDate>,
+    /// Nascimento dos outros bebês de uma gestação múltipla (Bebê 2 e 3).
+    sibling_birthdates: Vec<NaiveDate>,
+    /// Peso atual em gramas, para o percentil de Fenton.
+    weight_grams: Option<f64>,
+    sex: Option<growth::Sex>,
+}
+
+/// Resultado de um cálculo: o relatório de cada bebê, com os avisos e a nota
+/// informativa a exibir junto dele.
+struct Calculation {
+    report: Report,
+    /// Relatórios dos outros bebês de uma gestação múltipla, na ordem do formulário.
+    sibling_reports: Vec<Report>,
+    warnings: Vec<String>,
+    info: Option<String>,
+}
+
+/// Campos usados no cálculo, como digitados no formulário ou informados na linha
+/// de comando; campos opcionais vazios não são usados.
+#[derive(Default)]
+struct FormFields<'a> {
+    birth_date: &'a str,
+    gestational_weeks: &'a str,
+    gestational_days: &'a str,
+    lmp: &'a str,
+    observer: &'a str,
+    weight: &'a str,
+    sex: Option<growth::Sex>,
+    reference_date: &'a str,
+    /// Nascimento dos outros bebês da gestação, já limitados à quantidade escolhida.
+    sibling_birth_dates: &'a [String],
+}
+
+/// Campos de entrada do formulário, na ordem de preenchimento.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum InputField {
+    BirthDate,
+    Weeks,
+    Days,
+    Lmp,
+    ReferenceDate,
+}
+
+/// O que fazer ao pressionar 'Enter' em um campo.
+#[derive(Debug, PartialEq)]
+enum EnterAction {
+    Focus(InputField),
+    Calculate,
+}
+
+/// Decide a ação do 'Enter' em `field`. No modo "calcular", calcula de qualquer
+/// campo quando todas as entradas são válidas; caso contrário (ou no modo "próximo
+/// campo"), avança para o campo seguinte, e os dois últimos campos calculam.
+fn enter_action(field: InputField, behavior: EnterBehavior, inputs_valid: bool) -> EnterAction {
+    if behavior == EnterBehavior::Calculate && inputs_valid {
+        return EnterAction::Calculate;
+    }
+    match field {
+        InputField::BirthDate => EnterAction::Focus(InputField::Weeks),
+        InputField::Weeks => EnterAction::Focus(InputField::Days),
+        InputField::Days | InputField::Lmp | InputField::ReferenceDate => EnterAction::Calculate,
+    }
+}
+
+/// Semanas gestacionais como digitadas no formulário.
+#[derive(Debug, PartialEq)]
+enum WeeksInput {
+    Single(i32),
+    /// Intervalo de semanas, quando a datação é incerta (ex.: "31-33").
+    Range(i32, i32),
+    /// Semanas com casas decimais (ex.: "32,5"), já convertidas em semanas e dias.
+    Decimal {
+        weeks: i32,
+        days: i32,
+    },
+    /// Semanas e dias juntos (ex.: "32+4"), possivelmente colados com texto em volta.
+    Combined {
+        weeks: i32,
+        days: i32,
+    },
 }
 
-/// Armazena a idade corrigida calculada.
-struct CorrectedAge {
-    years: i32,
-    months: i32,
-    days: i32,
-    weeks: i64,
-    days_in_week: i64,
-    total_months: i64,
+/// Resultado da comparação entre a idade gestacional informada e a DUM.
+#[derive(Debug, PartialEq)]
+enum LmpCheck {
+    /// As duas fontes concordam dentro da tolerância.
+    Consistent,
+    /// A DUM indica outra idade gestacional ao nascer; `difference_days` é positivo
+    /// quando a DUM indica uma gestação mais longa que a informada.
+    Discrepant {
+        lmp_gestational_days: i64,
+        difference_days: i64,
+    },
+    /// A DUM é posterior ao nascimento, o que é impossível.
+    AfterBirth,
 }
 
 /// Estrutura principal da aplicação que armazena o estado.
@@ -33,322 +356,5217 @@ struct AgeCalculatorApp {
     birth_date_str: String,
     gestational_weeks_str: String,
     gestational_days_str: String,
-    result_text: Option<String>,
+    /// Valores dos botões de passo ao lado dos campos de semanas e de dias,
+    /// sincronizados com o texto dos campos (veja `stepper`).
+    gestational_weeks_value: i32,
+    gestational_days_value: i32,
+    lmp_str: String,
+    observer_str: String,
+    /// Peso atual opcional, em gramas, e o sexo do bebê, para o percentil de Fenton.
+    weight_str: String,
+    sex: Option<growth::Sex>,
+    /// Data de referência opcional (DD/MM/AAAA); vazia, usa a data atual.
+    reference_date_str: String,
+    /// Quantos bebês a gestação teve (1, 2 ou 3); os gêmeos compartilham a idade
+    /// gestacional informada.
+    babies: usize,
+    /// Nascimento do Bebê 2 e do Bebê 3; vazio, é o mesmo do Bebê 1.
+    sibling_birth_dates_str: [String; 2],
+    /// Ações destrutivas da sessão que ainda podem ser desfeitas.
+    undo_stack: UndoStack,
+    // O resultado é guardado já calculado e formatado a cada quadro, para que
+    // mudanças de configuração apareçam sem precisar recalcular.
+    report: Option<Report>,
+    /// Resultados dos outros bebês de uma gestação múltipla, na ordem do formulário.
+    sibling_reports: Vec<Report>,
     error_message: Option<String>,
-    clipboard: Option<arboard::Clipboard>,
+    warning_message: Option<String>,
+    /// Nota informativa sobre o resultado, que não indica problema nos dados.
+    info_message: Option<String>,
+    /// Sinal do último cálculo, exibido como um lampejo na área de resultados.
+    flash_cue: Option<Cue>,
+    /// Se o lampejo ainda está surgindo (verdadeiro) ou já está sumindo.
+    flash_rising: bool,
+    export_format: ExportFormat,
+    /// Resumo das alterações feitas pelo botão "Normalizar".
+    normalize_message: Option<String>,
+    clipboard: Option<clipboard::Clipboard>,
+    /// Se a área de transferência, indisponível ao iniciar, já foi procurada de novo.
+    clipboard_retried: bool,
+    roster: Roster,
+    show_roster: bool,
+    patient_name: String,
+    selected_patient: Option<usize>,
+    roster_message: Option<String>,
+    /// Exporta o cadastro sem nomes nem datas, para pesquisa.
+    deidentify_export: bool,
+    /// Chave sorteada a cada execução para gerar os identificadores anônimos.
+    session_key: std::hash::RandomState,
+    show_dashboard: bool,
+    dashboard_sort: DashboardSort,
+    show_reference_dates: bool,
+    /// Cálculos anteriores da sessão, do mais antigo para o mais recente.
+    history: Vec<HistoryEntry>,
+    show_history: bool,
+    show_about: bool,
+    /// Se o aviso de curvas de crescimento desatualizadas foi dispensado nesta sessão.
+    growth_notice_dismissed: bool,
+    /// Datas de referência digitadas, separadas por linha, espaço, vírgula ou ';'.
+    reference_dates_str: String,
+    reference_dates_message: Option<String>,
+    settings: Settings,
+    show_settings: bool,
+    settings_message: Option<String>,
+    /// Último título enviado à janela, para só enviá-lo de novo quando mudar.
+    window_title: String,
+    /// Ícone na bandeja do sistema (feature 'tray') e o último texto enviado a ele.
+    tray: Option<tray::Tray>,
+    tray_tooltip: String,
+    /// Escala aplicada no quadro anterior, para perceber mudanças pelos atalhos.
+    applied_zoom: Option<f32>,
 }
 
 /// Implementação padrão para 'AgeCalculatorApp'.
-/// Inicializa o estado da aplicação.
+/// Inicializa o estado da aplicação com os dados salvos do usuário.
 impl Default for AgeCalculatorApp {
     fn default() -> Self {
+        // Os campos principais começam com os valores da sessão anterior.
+        Self {
+            clipboard: clipboard::Clipboard::new().ok(),
+            ..Self::new(LastInputs::load(), Roster::load(), Settings::load())
+        }
+    }
+}
+
+impl AgeCalculatorApp {
+    /// Estado inicial a partir dos dados informados, sem ler arquivos nem abrir a
+    /// área de transferência.
+    fn new(last_inputs: LastInputs, roster: Roster, settings: Settings) -> Self {
+        // Uma gestação múltipla volta com o nascimento de cada um dos outros bebês.
+        let mut sibling_birth_dates_str: [String; 2] = Default::default();
+        let siblings = last_inputs.sibling_birth_dates.len().min(2);
+        for (field, date) in sibling_birth_dates_str
+            .iter_mut()
+            .zip(last_inputs.sibling_birth_dates)
+        {
+            *field = date;
+        }
         Self {
-            birth_date_str: String::new(),
-            gestational_weeks_str: String::new(),
-            gestational_days_str: String::new(),
-            result_text: None,
+            birth_date_str: last_inputs.birth_date,
+            gestational_weeks_str: last_inputs.gestational_weeks,
+            gestational_days_str: last_inputs.gestational_days,
+            gestational_weeks_value: 40,
+            gestational_days_value: 0,
+            lmp_str: String::new(),
+            observer_str: String::new(),
+            weight_str: String::new(),
+            sex: last_inputs.sex,
+            reference_date_str: String::new(),
+            babies: 1 + siblings,
+            sibling_birth_dates_str,
+            undo_stack: UndoStack::default(),
+            report: None,
+            sibling_reports: Vec::new(),
             error_message: None,
-            clipboard: arboard::Clipboard::new().ok(),
+            warning_message: None,
+            info_message: None,
+            flash_cue: None,
+            flash_rising: false,
+            export_format: ExportFormat::Text,
+            normalize_message: None,
+            clipboard: None,
+            clipboard_retried: false,
+            roster,
+            show_roster: false,
+            patient_name: String::new(),
+            selected_patient: None,
+            roster_message: None,
+            deidentify_export: false,
+            session_key: std::hash::RandomState::new(),
+            show_dashboard: false,
+            dashboard_sort: DashboardSort::Name,
+            show_reference_dates: false,
+            history: Vec::new(),
+            show_history: false,
+            show_about: false,
+            growth_notice_dismissed: false,
+            reference_dates_str: String::new(),
+            reference_dates_message: None,
+            settings,
+            show_settings: false,
+            settings_message: None,
+            window_title: String::new(),
+            tray: None,
+            tray_tooltip: String::new(),
+            applied_zoom: None,
         }
     }
 }
 
 /// Função principal que inicia a aplicação.
+#[cfg(not(target_arch = "wasm32"))]
 fn main() -> Result<(), eframe::Error> {
+    diagnostics::init();
+    #[cfg(all(windows, not(debug_assertions)))]
+    if std::env::args_os().len() > 1 {
+        cli::attach_parent_console();
+    }
+    // Com argumentos de cálculo, imprime o resultado e termina sem abrir a janela.
+    // A ajuda e os argumentos inválidos são exibidos pelo 'clap', que encerra o
+    // programa (código 2 para argumentos inválidos).
+    match cli::parse(std::env::args_os()) {
+        Ok(cli::Command::Gui) => {}
+        Ok(cli::Command::Calculate(args)) => std::process::exit(run_headless(args)),
+        Err(error) => error.exit(),
+    }
+    let lang = Settings::load().language;
+
     // Configurações da janela da aplicação.
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
-            .with_inner_size([440.0, 360.0]) // Define o tamanho da janela.
-            .with_resizable(false), // Impede que a janela seja redimensionada.
+            .with_inner_size([440.0, 360.0]) // Define o tamanho inicial da janela.
+            .with_min_inner_size([360.0, 280.0]) // Tamanho mínimo ao redimensionar.
+            .with_resizable(true),
         ..Default::default()
     };
 
     // Executa a aplicação nativa.
     eframe::run_native(
-        "Calculadora de Idade Gestacional do Bebê",
+        t("Calculadora de Idade Gestacional do Bebê", lang),
         options,
         Box::new(|_cc| Box::<AgeCalculatorApp>::default()),
     )
 }
 
+/// Ponto de entrada da versão para o navegador, que desenha a aplicação no
+/// `<canvas id="calculadora_canvas">` do `index.html`. Para compilar e servir
+/// localmente, com o alvo e o 'trunk' instalados:
+///
+/// ```sh
+/// rustup target add wasm32-unknown-unknown
+/// cargo install trunk
+/// trunk serve            # http://127.0.0.1:8080
+/// trunk build --release  # arquivos estáticos em 'dist/'
+/// ```
+///
+/// No navegador não há linha de comando, diálogos de arquivo nem área de
+/// transferência síncrona: os botões de copiar ficam desativados e as ações com
+/// arquivos não fazem nada (veja os módulos 'clipboard' e 'file_dialog').
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    diagnostics::init();
+    wasm_bindgen_futures::spawn_local(async {
+        eframe::WebRunner::new()
+            .start(
+                "calculadora_canvas",
+                eframe::WebOptions::default(),
+                Box::new(|_cc| Box::<AgeCalculatorApp>::default()),
+            )
+            .await
+            .expect("falha ao iniciar a aplicação no navegador");
+    });
+}
+
+/// Calcula sem abrir a janela, com as mesmas validações e o mesmo texto de resultado
+/// da interface. Usa as configurações padrão, e não as salvas pela interface, para
+/// que o resultado de um script não dependa do que foi escolhido na janela; também
+/// não lê os últimos valores nem o cadastro, nem usa a área de transferência. Os
+/// avisos e as notas vão para o 'stderr'. Retorna o código de saída: 0 com
+/// resultado, 1 com dados inválidos.
+#[cfg(not(target_arch = "wasm32"))]
+fn run_headless(args: cli::Args) -> i32 {
+    let settings = Settings::default();
+    let fields = FormFields {
+        birth_date: &args.birth,
+        gestational_weeks: &args.weeks,
+        gestational_days: &args.days,
+        reference_date: args.today.as_deref().unwrap_or_default(),
+        ..Default::default()
+    };
+    match parse_fields(&fields, &settings).and_then(|inputs| calculate_inputs(inputs, &settings)) {
+        Ok(calculation) => {
+            for message in calculation.warnings.iter().chain(&calculation.info) {
+                eprintln!("{}", message);
+            }
+            println!("{}", format_result_text(&calculation.report, &settings));
+            0
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            1
+        }
+    }
+}
+
 impl AgeCalculatorApp {
-    /// Realiza o cálculo da idade cronológica e corrigida.
-    fn calculate(&mut self) {
-        // Limpa os resultados e mensagens de erro anteriores.
-        self.result_text = None;
+    /// Campos do formulário a serem restaurados na próxima sessão, incluindo os
+    /// outros bebês de uma gestação múltipla.
+    fn last_inputs(&self) -> LastInputs {
+        LastInputs {
+            birth_date: self.birth_date_str.clone(),
+            gestational_weeks: self.gestational_weeks_str.clone(),
+            gestational_days: self.gestational_days_str.clone(),
+            sex: self.sex,
+            sibling_birth_dates: self.sibling_birth_dates_str[..self.babies - 1].to_vec(),
+        }
+    }
+
+    /// Cria, atualiza ou remove o ícone da bandeja conforme a configuração. Se o
+    /// sistema recusar o ícone, a criação só é tentada de novo quando o texto mudar.
+    fn update_tray(&mut self, tooltip: &str) {
+        if !(tray::SUPPORTED && self.settings.tray_icon) {
+            self.tray = None;
+            self.tray_tooltip.clear();
+            return;
+        }
+        if tooltip == self.tray_tooltip {
+            return;
+        }
+        match &self.tray {
+            Some(tray) => tray.set_tooltip(tooltip),
+            None => self.tray = tray::Tray::new(tooltip),
+        }
+        self.tray_tooltip = tooltip.to_string();
+    }
+
+    /// Limpa os resultados e as mensagens de erro, de aviso e informativas.
+    fn clear_results(&mut self) {
+        self.report = None;
+        self.sibling_reports.clear();
         self.error_message = None;
+        self.warning_message = None;
+        self.info_message = None;
+        self.normalize_message = None;
+    }
 
-        // Valida e converte a data de nascimento.
-        let birthdate = match NaiveDate::parse_from_str(&self.birth_date_str, "%d/%m/%Y") {
-            Ok(date) => date,
-            Err(_) => {
-                self.error_message = Some("Formato de data inválido. Use DD/MM/AAAA.".to_string());
-                return;
+    /// Limpa todos os campos e resultados, guardando os campos para "Desfazer".
+    fn clear_form(&mut self) {
+        self.undo_stack.push(UndoEntry::ClearedForm(FormSnapshot {
+            birth_date: std::mem::take(&mut self.birth_date_str),
+            gestational_weeks: std::mem::take(&mut self.gestational_weeks_str),
+            gestational_days: std::mem::take(&mut self.gestational_days_str),
+            lmp: std::mem::take(&mut self.lmp_str),
+            observer: std::mem::take(&mut self.observer_str),
+            weight: std::mem::take(&mut self.weight_str),
+            reference_date: std::mem::take(&mut self.reference_date_str),
+            sibling_birth_dates: std::mem::take(&mut self.sibling_birth_dates_str),
+        }));
+        self.clear_results();
+    }
+
+    /// Desfaz a ação destrutiva mais recente: restaura os campos apagados (e refaz
+    /// o cálculo) ou devolve ao cadastro o paciente excluído.
+    fn undo(&mut self) {
+        match self.undo_stack.pop() {
+            Some(UndoEntry::ClearedForm(snapshot)) => {
+                self.birth_date_str = snapshot.birth_date;
+                self.gestational_weeks_str = snapshot.gestational_weeks;
+                self.gestational_days_str = snapshot.gestational_days;
+                self.lmp_str = snapshot.lmp;
+                self.observer_str = snapshot.observer;
+                self.weight_str = snapshot.weight;
+                self.reference_date_str = snapshot.reference_date;
+                self.sibling_birth_dates_str = snapshot.sibling_birth_dates;
+                self.calculate();
             }
-        };
+            Some(UndoEntry::DeletedPatient { index, patient }) => {
+                let index = undo::restore_patient(&mut self.roster.patients, index, patient);
+                if let Some(selected) = self.selected_patient.filter(|&s| s >= index) {
+                    self.selected_patient = Some(selected + 1);
+                }
+                self.save_roster();
+            }
+            None => {}
+        }
+    }
 
-        // Valida e converte as semanas gestacionais.
-        let gestational_weeks = match i32::from_str(&self.gestational_weeks_str) {
-            Ok(val) => val,
-            Err(_) => {
-                self.error_message = Some("Idade gestacional deve ser um número.".to_string());
-                return;
+    /// Arruma as entradas coladas de outros sistemas, deixando cada campo na forma
+    /// canônica: remove espaços, completa as datas com zeros e ano com quatro
+    /// dígitos e separa idades gestacionais combinadas ("32+4" em 32 semanas e 4
+    /// dias). Campos que não podem ser interpretados ficam como estão e são
+    /// apontados pelo cálculo.
+    fn normalize_inputs(&mut self) {
+        let lang = self.settings.language;
+        let today = Utc::now().date_naive();
+        let mut changes = Vec::new();
+
+        let mut birth_date = self.birth_date_str.trim().to_string();
+        if let Some(date) = dates::normalize_date(&birth_date, today, dates::system_date_order()) {
+            birth_date = date;
+        }
+        let mut lmp = self.lmp_str.trim().to_string();
+        if let Some(date) = dates::normalize_date(&lmp, today, dates::system_date_order()) {
+            lmp = date;
+        }
+        let mut reference_date = self.reference_date_str.trim().to_string();
+        if let Some(date) =
+            dates::normalize_date(&reference_date, today, dates::system_date_order())
+        {
+            reference_date = date;
+        }
+        let mut weeks = self.gestational_weeks_str.trim().to_string();
+        let mut days = self.gestational_days_str.trim().to_string();
+        if let Some((combined_weeks, combined_days)) = split_gestational_age(&weeks) {
+            weeks = combined_weeks.to_string();
+            days = combined_days.to_string();
+        } else if let Ok(number) = i32::from_str(&weeks) {
+            weeks = number.to_string();
+        }
+        if let Ok(number) = i32::from_str(&days) {
+            days = number.to_string();
+        }
+
+        for (label, field, normalized) in [
+            ("Data de nascimento", &mut self.birth_date_str, birth_date),
+            ("Semanas", &mut self.gestational_weeks_str, weeks),
+            ("Dias", &mut self.gestational_days_str, days),
+            ("DUM", &mut self.lmp_str, lmp),
+            (
+                "Data de referência",
+                &mut self.reference_date_str,
+                reference_date,
+            ),
+        ] {
+            if *field != normalized {
+                changes.push(format!(
+                    "{}: \"{}\" → \"{}\"",
+                    t(label, lang),
+                    field,
+                    normalized
+                ));
+                *field = normalized;
             }
-        };
+        }
 
-        // Valida e converte os dias gestacionais.
-        let gestational_days = match i32::from_str(&self.gestational_days_str) {
-            Ok(val) => val,
-            Err(_) => {
-                self.error_message =
-                    Some("Dias na semana de nascimento devem ser um número.".to_string());
-                return;
+        self.normalize_message = Some(if changes.is_empty() {
+            t("Nenhum campo precisou ser alterado.", lang).to_string()
+        } else {
+            tf("Campos normalizados:\n{}", lang, &[&changes.join("\n")])
+        });
+    }
+
+    /// Valida e converte as entradas do formulário, retornando a mensagem de erro
+    /// a ser exibida caso alguma seja inválida.
+    fn parse_inputs(&self) -> Result<Inputs, String> {
+        parse_fields(&self.form_fields(), &self.settings)
+    }
+
+    /// Texto dos campos do formulário usados no cálculo. Dos outros bebês, só entram
+    /// os da gestação escolhida.
+    fn form_fields(&self) -> FormFields<'_> {
+        FormFields {
+            birth_date: &self.birth_date_str,
+            gestational_weeks: &self.gestational_weeks_str,
+            gestational_days: &self.gestational_days_str,
+            lmp: &self.lmp_str,
+            observer: &self.observer_str,
+            weight: &self.weight_str,
+            sex: self.sex,
+            reference_date: &self.reference_date_str,
+            sibling_birth_dates: &self.sibling_birth_dates_str[..self.babies - 1],
+        }
+    }
+
+    /// Acrescenta um aviso aos já exibidos.
+    fn add_warning(&mut self, warning: String) {
+        self.warning_message = Some(match self.warning_message.take() {
+            Some(previous) => format!("{}\n{}", previous, warning),
+            None => warning,
+        });
+    }
+
+    /// Realiza o cálculo da idade cronológica e corrigida.
+    fn calculate(&mut self) {
+        // Limpa os resultados e mensagens de erro anteriores.
+        self.clear_results();
+        let calculation = self
+            .parse_inputs()
+            .and_then(|inputs| calculate_inputs(inputs, &self.settings));
+        match calculation {
+            Ok(Calculation {
+                report,
+                sibling_reports,
+                warnings,
+                info,
+            }) => {
+                for warning in warnings {
+                    self.add_warning(warning);
+                }
+                self.info_message = info;
+                self.add_to_history(&report);
+                self.report = Some(report);
+                self.sibling_reports = sibling_reports;
             }
-        };
+            Err(message) => {
+                log::debug!("Entradas rejeitadas: {}", message);
+                self.error_message = Some(message);
+            }
+        }
+    }
 
-        // Obtém a data atual.
-        let today = Utc::now().date_naive();
+    /// Acrescenta o cálculo ao histórico, a menos que repita o último (como no
+    /// recálculo automático sem mudança no resultado).
+    fn add_to_history(&mut self, report: &Report) {
+        let entry = history_entry(
+            report,
+            &self.gestational_weeks_str,
+            &self.gestational_days_str,
+            self.settings.language,
+        );
+        if self
+            .history
+            .last()
+            .is_some_and(|last| last.inputs == entry.inputs && last.result == entry.result)
+        {
+            return;
+        }
+        if self.history.len() == HISTORY_MAX_ENTRIES {
+            self.history.remove(0);
+        }
+        self.history.push(entry);
+    }
 
-        // Calcula a idade cronológica.
-        let chronological_age = calculate_chronological_age(birthdate, today);
-
-        // Calcula a idade corrigida.
-        let corrected_age =
-            calculate_corrected_age(birthdate, today, gestational_weeks, gestational_days);
-
-        // Formata e exibe o resultado.
-        self.result_text = Some(format!(
-            "Idade Cronológica: {} semanas ({} meses)\nIdade Corrigida: {} semanas ({} meses) e {} dias\nIdade Corrigida (Anos): {} anos, {} meses e {} dias",
-            chronological_age.total_weeks,
-            chronological_age.total_months,
-            corrected_age.weeks,
-            corrected_age.total_months,
-            corrected_age.days_in_week,
-            corrected_age.years,
-            corrected_age.months,
-            corrected_age.days
-        ));
+    /// Calcula a pedido do usuário e sinaliza o resultado com um lampejo e um som,
+    /// conforme as configurações. O recálculo automático não usa esses sinais.
+    fn calculate_and_confirm(&mut self) {
+        self.calculate();
+        let cue = if self.report.is_some() {
+            Cue::Success
+        } else {
+            Cue::Error
+        };
+        if self.settings.flash_on_calculate {
+            self.flash_cue = Some(cue);
+            self.flash_rising = true;
+        }
+        if self.settings.sound_on_calculate {
+            feedback::play(cue);
+        }
     }
-}
 
-/// Implementa a lógica de atualização da interface gráfica.
-impl eframe::App for AgeCalculatorApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Painel do rodapé com hyperlink.
-        egui::TopBottomPanel::bottom("footer_panel").show(ctx, |ui| {
-            ui.vertical_centered(|ui| {
-                ui.add_space(5.0);
-                ui.hyperlink_to(
-                    "Desenvolvido em Rust por Paulo A V Munhoz",
-                    "https://www.linkedin.com/in/paulomunhoz/",
-                );
-                ui.add_space(5.0);
-            });
+    /// Copia o resultado, incluindo as consultas de seguimento, como texto simples.
+    fn copy_result_text(&mut self) {
+        let Some(report) = &self.report else {
+            return;
+        };
+        let text = format_babies_text(report, &self.sibling_reports, self.settings.language, |r| {
+            format_export_text(r, &self.settings)
         });
+        self.copy_text(text);
+    }
 
-        // Painel central onde a maior parte da UI é renderizada.
-        egui::CentralPanel::default().show(ctx, |ui| {
-            // Título da aplicação.
-            ui.vertical_centered(|ui| {
-                ui.add_space(10.0);
-                ui.heading("Calculadora de Idade Gestacional do Bebê");
-            });
-            ui.add_space(15.0);
+    /// Tenta abrir de novo a área de transferência, se ela não estava disponível ao
+    /// iniciar (em algumas sessões Wayland, ela só responde depois que a janela
+    /// existe). A nova tentativa é feita uma única vez.
+    fn retry_clipboard(&mut self) {
+        if self.clipboard.is_none() && !self.clipboard_retried {
+            self.clipboard_retried = true;
+            self.clipboard = clipboard::Clipboard::new().ok();
+            log::debug!(
+                "Nova tentativa de abrir a área de transferência: {}",
+                if self.clipboard.is_some() {
+                    "disponível"
+                } else {
+                    "indisponível"
+                }
+            );
+        }
+    }
 
-            // IDs para os campos de entrada, para controle de foco.
-            let birth_date_id = ui.id().with("birth_date_input");
-            let weeks_id = ui.id().with("weeks_input");
-            let days_id = ui.id().with("days_input");
-            let mut birth_date_response = None;
-            let mut weeks_response = None;
-            let mut days_response = None;
+    /// Copia um texto qualquer para a área de transferência, informando falhas.
+    fn copy_text(&mut self, text: String) {
+        let lang = self.settings.language;
+        self.retry_clipboard();
+        if let Some(clipboard) = &mut self.clipboard {
+            match set_text_checked(clipboard, &text) {
+                Ok(false) => {}
+                Ok(true) => self.add_warning(
+                    t(
+                        "A área de transferência não aceitou acentos: o texto foi copiado sem eles.",
+                        lang,
+                    )
+                    .to_string(),
+                ),
+                Err(e) => {
+                    self.error_message =
+                        Some(clipboard_error_message(ClipboardOp::Text, &e, lang));
+                }
+            }
+        } else {
+            self.error_message = Some(t("Área de transferência não disponível.", lang).to_string());
+        }
+    }
 
-            // Grid para alinhar os rótulos e campos de entrada.
-            ui.vertical_centered(|ui| {
-                egui::Grid::new("input_grid")
-                    .num_columns(2)
-                    .spacing([10.0, 12.0])
-                    .show(ui, |ui| {
-                        // Campo para a data de nascimento.
-                        ui.label("Data de Nascimento (DD/MM/AAAA):");
-                        birth_date_response = Some(ui.add(
-                            egui::TextEdit::singleline(&mut self.birth_date_str).id(birth_date_id),
+    /// Copia o resultado como fragmento HTML (tabela), para colar em prontuários
+    /// com texto formatado. Se a área de transferência não aceitar HTML, copia
+    /// o texto simples.
+    fn copy_result_html(&mut self) {
+        self.retry_clipboard();
+        let Some(report) = &self.report else {
+            return;
+        };
+        let lang = self.settings.language;
+        let text = format_babies_text(report, &self.sibling_reports, lang, |r| {
+            format_export_text(r, &self.settings)
+        });
+        let html = format_babies_html(report, &self.sibling_reports, &self.settings);
+        if let Some(clipboard) = &mut self.clipboard {
+            if let Err(html_error) = clipboard.set_html(html, Some(text.clone())) {
+                match clipboard.set_text(text) {
+                    // O texto simples foi copiado no lugar do HTML: apenas avisa.
+                    Ok(()) => {
+                        self.warning_message = Some(tf(
+                            "{} O resultado foi copiado como texto simples.",
+                            lang,
+                            &[&clipboard_error_message(
+                                ClipboardOp::Html,
+                                &html_error,
+                                lang,
+                            )],
                         ));
-                        ui.end_row();
+                    }
+                    Err(e) => {
+                        self.error_message =
+                            Some(clipboard_error_message(ClipboardOp::Text, &e, lang));
+                    }
+                }
+            }
+        } else {
+            self.error_message = Some(t("Área de transferência não disponível.", lang).to_string());
+        }
+    }
 
-                        // Campo para as semanas gestacionais.
-                        ui.label("Idade Gestacional (semanas):");
-                        weeks_response = Some(
-                            ui.add(
-                                egui::TextEdit::singleline(&mut self.gestational_weeks_str)
-                                    .id(weeks_id),
-                            ),
-                        );
-                        ui.end_row();
+    /// Grava o cadastro de pacientes, informando falhas na janela do cadastro.
+    fn save_roster(&mut self) {
+        if let Err(e) = self.roster.save() {
+            self.roster_message = Some(tf(
+                "Falha ao salvar o cadastro: {}",
+                self.settings.language,
+                &[&e],
+            ));
+        }
+    }
 
-                        // Campo para os dias na semana de nascimento.
-                        ui.label("Dias na Semana de Nascimento:");
-                        days_response = Some(ui.add(
-                            egui::TextEdit::singleline(&mut self.gestational_days_str).id(days_id),
-                        ));
-                        ui.end_row();
-                    });
-            });
+    /// Cria um paciente a partir do nome e das entradas atuais do formulário.
+    fn patient_from_inputs(&self) -> Result<Patient, String> {
+        let name = self.patient_name.trim();
+        if name.is_empty() {
+            return Err(t("Informe o nome do paciente.", self.settings.language).to_string());
+        }
+        let inputs = self.parse_inputs()?;
+        Ok(Patient {
+            name: name.to_string(),
+            birthdate: inputs.birthdate,
+            gestational_weeks: inputs.gestational_weeks,
+            gestational_days: inputs.gestational_days,
+            observer: inputs.observer,
+        })
+    }
 
-            // Lógica para mudar o foco entre os campos de entrada ao pressionar 'Enter'.
-            if birth_date_response.unwrap().lost_focus()
-                && ctx.input(|i| i.key_pressed(egui::Key::Enter))
-            {
-                ctx.memory_mut(|m| m.request_focus(weeks_id));
+    /// Preenche o formulário com os dados de um paciente do cadastro e recalcula.
+    fn select_patient(&mut self, index: usize) {
+        let patient = &self.roster.patients[index];
+        self.patient_name = patient.name.clone();
+        self.birth_date_str = patient.birthdate.format("%d/%m/%Y").to_string();
+        self.gestational_weeks_str = patient.gestational_weeks.to_string();
+        self.gestational_days_str = patient.gestational_days.to_string();
+        self.observer_str = patient.observer.clone().unwrap_or_default();
+        self.selected_patient = Some(index);
+        self.calculate();
+    }
+
+    /// Adiciona um novo paciente com os dados do formulário ao cadastro.
+    fn add_patient(&mut self) -> Result<(), String> {
+        let patient = self.patient_from_inputs()?;
+        self.roster.patients.push(patient);
+        self.selected_patient = Some(self.roster.patients.len() - 1);
+        self.roster_message = None;
+        self.save_roster();
+        Ok(())
+    }
+
+    /// Conteúdo da janela do cadastro de pacientes.
+    fn roster_ui(&mut self, ui: &mut egui::Ui) {
+        let lang = self.settings.language;
+        let name_id = egui::Id::new("patient_name");
+        ui.horizontal(|ui| {
+            ui.label(t("Nome:", lang));
+            ui.add(egui::TextEdit::singleline(&mut self.patient_name).id(name_id));
+        });
+        ui.horizontal(|ui| {
+            // Adiciona um novo paciente com os dados do formulário.
+            if ui.button(t("Adicionar", lang)).clicked() {
+                if let Err(message) = self.add_patient() {
+                    self.roster_message = Some(message);
+                }
             }
-            if weeks_response.unwrap().lost_focus()
-                && ctx.input(|i| i.key_pressed(egui::Key::Enter))
+            // Calcula e já salva o paciente, em um só passo. Sem nome, pede o nome
+            // antes de salvar; o resultado do cálculo continua na tela.
+            if ui
+                .button(t("Calcular e adicionar", lang))
+                .on_hover_text(t("Calcula as idades e salva o paciente no cadastro.", lang))
+                .clicked()
             {
-                ctx.memory_mut(|m| m.request_focus(days_id));
+                self.calculate();
+                if self.report.is_some() {
+                    match self.add_patient() {
+                        Ok(()) => {
+                            self.roster_message = Some(tf(
+                                "{} adicionado(a) ao cadastro.",
+                                lang,
+                                &[&self.patient_name],
+                            ));
+                        }
+                        Err(message) => {
+                            self.roster_message = Some(message);
+                            ui.memory_mut(|m| m.request_focus(name_id));
+                        }
+                    }
+                } else {
+                    self.roster_message = Some(
+                        t("Corrija os dados do formulário antes de salvar.", lang).to_string(),
+                    );
+                }
             }
-            if days_response.unwrap().lost_focus() && ctx.input(|i| i.key_pressed(egui::Key::Enter))
+            // Substitui o paciente selecionado pelos dados do formulário.
+            if ui
+                .add_enabled(
+                    self.selected_patient.is_some(),
+                    egui::Button::new(t("Atualizar selecionado", lang)),
+                )
+                .clicked()
             {
-                self.calculate();
+                match (self.patient_from_inputs(), self.selected_patient) {
+                    (Ok(patient), Some(index)) => {
+                        self.roster.patients[index] = patient;
+                        self.roster_message = None;
+                        self.save_roster();
+                    }
+                    (Err(message), _) => self.roster_message = Some(message),
+                    _ => {}
+                }
             }
-
-            ui.add_space(15.0);
-
-            // Botões de "Calcular" e "Limpar".
-            ui.vertical_centered(|ui| {
-                ui.horizontal(|ui| {
-                    let button_width = 100.0;
-                    let spacing = ui.spacing().item_spacing.x;
-                    let total_width = (button_width * 2.0) + spacing;
-                    let left_space = (ui.available_width() - total_width).max(0.0) / 2.0;
-                    ui.add_space(left_space);
-
-                    if ui
-                        .add_sized([button_width, 30.0], egui::Button::new("Calcular"))
-                        .clicked()
-                    {
-                        self.calculate();
+        });
+        ui.horizontal(|ui| {
+            if ui.button(t("Painel do Dia", lang)).clicked() {
+                self.show_dashboard = true;
+            }
+            if ui.button(t("Importar...", lang)).clicked() {
+                if let Some(path) = file_dialog::FileDialog::new()
+                    .add_filter("JSON", &["json"])
+                    .pick_file()
+                {
+                    match self.roster.import(&path) {
+                        Ok(count) => {
+                            self.roster_message =
+                                Some(tf("{} paciente(s) importado(s).", lang, &[&count]));
+                            self.save_roster();
+                        }
+                        Err(e) => {
+                            self.roster_message = Some(tf("Falha ao importar: {}", lang, &[&e]));
+                        }
                     }
-                    if ui
-                        .add_sized([button_width, 30.0], egui::Button::new("Limpar"))
-                        .clicked()
-                    {
-                        // Limpa todos os campos e resultados.
-                        self.birth_date_str.clear();
-                        self.gestational_weeks_str.clear();
-                        self.gestational_days_str.clear();
-                        self.result_text = None;
-                        self.error_message = None;
+                }
+            }
+            if ui.button(t("Exportar...", lang)).clicked() {
+                let file_name = if self.deidentify_export {
+                    "pacientes_anonimizados.json"
+                } else {
+                    "pacientes.json"
+                };
+                if let Some(path) = file_dialog::FileDialog::new()
+                    .add_filter("JSON", &["json"])
+                    .set_file_name(file_name)
+                    .save_file()
+                {
+                    let result = if self.deidentify_export {
+                        let today = Utc::now().date_naive();
+                        let records = deidentified_records(
+                            &self.roster.patients,
+                            today,
+                            &self.session_key,
+                            self.settings.correction_term.weeks(),
+                        );
+                        Roster::export_deidentified(&path, &records)
+                    } else {
+                        self.roster.export(&path)
+                    };
+                    if let Err(e) = result {
+                        self.roster_message = Some(tf("Falha ao exportar: {}", lang, &[&e]));
                     }
-                });
-            });
-
-            ui.add_space(15.0);
+                }
+            }
+        });
+        ui.checkbox(
+            &mut self.deidentify_export,
+            t("Exportar sem identificação (pesquisa)", lang),
+        )
+        .on_hover_text(t(
+            "Omite nomes e datas; cada paciente recebe um código anônimo, o mesmo \
+             durante esta sessão.",
+            lang,
+        ));
+        if let Some(message) = &self.roster_message {
+            ui.label(message);
+        }
+        ui.separator();
 
-            // Exibe mensagens de erro ou os resultados.
-            ui.vertical_centered(|ui| {
-                if let Some(error) = &self.error_message {
-                    ui.colored_label(egui::Color32::RED, error);
+        // Lista de pacientes. As ações são aplicadas após o laço para não alterar a
+        // lista durante a iteração.
+        let mut to_select = None;
+        let mut to_delete = None;
+        egui::ScrollArea::vertical()
+            .max_height(200.0)
+            .show(ui, |ui| {
+                if self.roster.patients.is_empty() {
+                    ui.label(t("Nenhum paciente cadastrado.", lang));
                 }
-                if let Some(result) = &self.result_text {
-                    let mut result_clone = result.clone();
-                    // Campo de texto de múltiplas linhas para exibir o resultado.
-                    ui.text_edit_multiline(&mut result_clone).enabled = false;
-                    ui.add_space(10.0);
-                    // Botão para copiar o resultado.
-                    if ui
-                        .add_sized([150.0, 30.0], egui::Button::new("Copiar Resultado"))
-                        .clicked()
-                    {
-                        if let Some(clipboard) = &mut self.clipboard {
-                            if let Err(e) = clipboard.set_text(result.clone()) {
-                                self.error_message = Some(format!("Falha ao copiar: {}", e));
-                            }
-                        } else {
-                            self.error_message =
-                                Some("Área de transferência não disponível.".to_string());
+                for (index, patient) in self.roster.patients.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        let text = format!(
+                            "{} — {} — {}s{}d",
+                            patient.name,
+                            patient.birthdate.format("%d/%m/%Y"),
+                            patient.gestational_weeks,
+                            patient.gestational_days
+                        );
+                        if ui
+                            .selectable_label(self.selected_patient == Some(index), text)
+                            .clicked()
+                        {
+                            to_select = Some(index);
                         }
-                    }
+                        if ui.small_button(t("Excluir", lang)).clicked() {
+                            to_delete = Some(index);
+                        }
+                    });
                 }
             });
-        });
+        if let Some(index) = to_select {
+            self.select_patient(index);
+        }
+        if let Some(index) = to_delete {
+            let patient = self.roster.patients.remove(index);
+            self.undo_stack
+                .push(UndoEntry::DeletedPatient { index, patient });
+            self.selected_patient = match self.selected_patient {
+                Some(selected) if selected == index => None,
+                Some(selected) if selected > index => Some(selected - 1),
+                other => other,
+            };
+            self.save_roster();
+        }
     }
-}
-
-/// Calcula a idade cronológica com base na data de nascimento e na data atual.
-fn calculate_chronological_age(birthdate: NaiveDate, today: NaiveDate) -> ChronologicalAge {
-    let mut years = today.year() - birthdate.year();
-    let mut months = today.month() as i32 - birthdate.month() as i32;
-    let mut days = today.day() as i32 - birthdate.day() as i32;
 
-    // Ajusta os dias e meses se forem negativos.
-    if days < 0 {
-        months -= 1;
-        let prev_month = today.with_day(1).unwrap() - chrono::Duration::days(1);
-        days += prev_month.day() as i32;
-    }
-    if months < 0 {
-        years -= 1;
-        months += 12;
-    }
+    /// Conteúdo do painel com as idades atuais de todos os pacientes do cadastro.
+    fn dashboard_ui(&mut self, ui: &mut egui::Ui) {
+        let lang = self.settings.language;
+        ui.horizontal(|ui| {
+            ui.label(t("Ordenar por:", lang));
+            ui.radio_value(
+                &mut self.dashboard_sort,
+                DashboardSort::Name,
+                t("Nome", lang),
+            );
+            ui.radio_value(
+                &mut self.dashboard_sort,
+                DashboardSort::CorrectedAge,
+                t("Idade corrigida", lang),
+            );
+        });
+        // Recalcula as idades de cada paciente para hoje.
+        let today = Utc::now().date_naive();
+        let rows = dashboard_rows(
+            &self.roster.patients,
+            today,
+            &self.dashboard_sort,
+            self.settings.month_count,
+            self.settings.correction_term.weeks(),
+        );
 
-    // Calcula o total de dias, semanas e meses.
-    let total_days = today.signed_duration_since(birthdate).num_days();
-    let total_weeks = total_days / 7;
-    let total_months = (total_days as f64 / 30.4375).floor() as i64;
+        // Gera o resumo semanal em arquivo, na pasta escolhida pelo usuário.
+        if ui
+            .add_enabled(
+                !rows.is_empty(),
+                egui::Button::new(t("Gerar resumo semanal...", lang)),
+            )
+            .clicked()
+        {
+            if let Some(folder) = file_dialog::FileDialog::new().pick_folder() {
+                let path = folder.join(format!("resumo_semanal_{}.txt", today.format("%Y-%m-%d")));
+                self.roster_message = Some(
+                    match std::fs::write(&path, format_weekly_digest(&rows, today, lang)) {
+                        Ok(()) => tf("Resumo salvo em {}", lang, &[&path.display()]),
+                        Err(e) => tf("Falha ao salvar o resumo: {}", lang, &[&e]),
+                    },
+                );
+            }
+        }
+        if let Some(message) = &self.roster_message {
+            ui.label(message);
+        }
+        ui.separator();
 
-    ChronologicalAge {
-        years,
-        months,
-        days,
-        total_weeks,
-        total_months,
+        if rows.is_empty() {
+            ui.label(t("Nenhum paciente cadastrado.", lang));
+            return;
+        }
+        egui::ScrollArea::vertical()
+            .max_height(250.0)
+            .show(ui, |ui| {
+                egui::Grid::new("dashboard_grid")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(t("Paciente", lang));
+                        ui.strong(t("Idade Cronológica", lang));
+                        ui.strong(t("Idade Corrigida", lang));
+                        ui.end_row();
+                        for (patient, report) in &rows {
+                            ui.label(&patient.name);
+                            ui.label(tf(
+                                "{} semanas ({} meses)",
+                                lang,
+                                &[
+                                    &report.chronological.total_weeks,
+                                    &report.chronological.total_months,
+                                ],
+                            ));
+                            ui.label(tf(
+                                "{} semanas e {} dias",
+                                lang,
+                                &[&report.corrected.weeks, &report.corrected.days_in_week],
+                            ));
+                            ui.end_row();
+                        }
+                    });
+            });
     }
-}
 
-/// Calcula a idade corrigida, ajustando para a prematuridade.
-fn calculate_corrected_age(
-    birthdate: NaiveDate,
-    today: NaiveDate,
+    /// Conteúdo da janela "Sobre": a versão do programa e das tabelas de referência.
+    fn about_ui(&self, ui: &mut egui::Ui) {
+        let lang = self.settings.language;
+        ui.heading(t("Calculadora de Idade Gestacional do Bebê", lang));
+        ui.label(tf("Versão {}", lang, &[&env!("CARGO_PKG_VERSION")]));
+        ui.separator();
+        ui.label(tf(
+            "Curvas de crescimento: {}",
+            lang,
+            &[&growth::TABLE_VERSION],
+        ));
+        if let Some(latest) = growth::newer_version() {
+            ui.label(tf("Versão mais recente conhecida: {}", lang, &[&latest]));
+        }
+        ui.small(t(
+            "Os resultados servem de orientação e não substituem a avaliação clínica.",
+            lang,
+        ));
+    }
+
+    /// Conteúdo da janela do histórico de cálculos, do mais recente para o mais antigo.
+    fn history_ui(&mut self, ui: &mut egui::Ui) {
+        let lang = self.settings.language;
+        if self.history.is_empty() {
+            ui.label(t("Nenhum cálculo nesta sessão.", lang));
+            return;
+        }
+        egui::ScrollArea::vertical()
+            .max_height(300.0)
+            .show(ui, |ui| {
+                for entry in self.history.iter().rev() {
+                    ui.label(
+                        egui::RichText::new(format!("{} — {}", entry.time, entry.inputs)).strong(),
+                    );
+                    ui.label(&entry.result);
+                    ui.separator();
+                }
+            });
+        if ui.button(t("Limpar Histórico", lang)).clicked() {
+            self.history.clear();
+        }
+    }
+
+    /// Conteúdo da janela que compara as idades do bebê em várias datas de
+    /// referência, por exemplo para reconstruir a linha do tempo de consultas antigas.
+    fn reference_dates_ui(&mut self, ui: &mut egui::Ui) {
+        let lang = self.settings.language;
+        ui.label(t("Datas de referência (DD/MM/AAAA), uma por linha:", lang));
+        ui.add(
+            egui::TextEdit::multiline(&mut self.reference_dates_str)
+                .desired_rows(4)
+                .desired_width(f32::INFINITY),
+        );
+
+        let inputs = match self.parse_inputs() {
+            Ok(inputs) => inputs,
+            Err(message) => {
+                ui.colored_label(egui::Color32::RED, message);
+                return;
+            }
+        };
+        let today = Utc::now().date_naive();
+        let (reference_dates, invalid) = parse_reference_dates(&self.reference_dates_str, today);
+        let mut problems: Vec<String> = invalid
+            .iter()
+            .map(|entry| tf("Data inválida: \"{}\"", lang, &[entry]))
+            .collect();
+        let mut rows = Vec::new();
+        for date in reference_dates {
+            if date < inputs.birthdate {
+                problems.push(tf(
+                    "{} é anterior ao nascimento.",
+                    lang,
+                    &[&date.format(dates::DATE_FORMAT)],
+                ));
+            } else {
+                rows.push(compute_all(
+                    inputs.birthdate,
+                    inputs.gestational_weeks,
+                    inputs.gestational_days,
+                    date,
+                    self.settings.month_count,
+                    self.settings.correction_term.weeks(),
+                ));
+            }
+        }
+        for problem in &problems {
+            ui.colored_label(egui::Color32::RED, problem);
+        }
+
+        if ui
+            .add_enabled(!rows.is_empty(), egui::Button::new(t("Exportar...", lang)))
+            .clicked()
+        {
+            if let Some(path) = file_dialog::FileDialog::new()
+                .set_file_name("idades_por_data.txt")
+                .save_file()
+            {
+                self.reference_dates_message = Some(
+                    match std::fs::write(&path, format_reference_grid(&rows, lang)) {
+                        Ok(()) => tf("Tabela salva em {}", lang, &[&path.display()]),
+                        Err(e) => tf("Falha ao exportar: {}", lang, &[&e]),
+                    },
+                );
+            }
+        }
+        if let Some(message) = &self.reference_dates_message {
+            ui.label(message);
+        }
+        ui.separator();
+
+        egui::ScrollArea::vertical()
+            .max_height(250.0)
+            .show(ui, |ui| {
+                egui::Grid::new("reference_dates_grid")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(t("Data", lang));
+                        ui.strong(t("Idade Cronológica", lang));
+                        ui.strong(t("Idade Corrigida", lang));
+                        ui.end_row();
+                        for (date, chronological, corrected) in reference_grid_cells(&rows, lang) {
+                            ui.label(date);
+                            ui.label(chronological);
+                            ui.label(corrected);
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    /// Grava as configurações. O resultado exibido é reformatado automaticamente
+    /// no próximo quadro, sem recalcular.
+    fn apply_settings(&mut self) {
+        if let Err(e) = self.settings.save() {
+            self.error_message = Some(tf(
+                "Falha ao salvar as configurações: {}",
+                self.settings.language,
+                &[&e],
+            ));
+        }
+    }
+
+    /// Troca o modo de exibição do resultado, lembrando a escolha.
+    fn set_display_mode(&mut self, mode: DisplayMode) {
+        self.settings.display_mode = mode;
+        self.apply_settings();
+    }
+
+    /// Conteúdo da janela de configurações. As alterações são salvas imediatamente
+    /// e o resultado exibido é recalculado para refleti-las.
+    fn settings_ui(&mut self, ui: &mut egui::Ui) {
+        let lang = self.settings.language;
+        let mut changed = false;
+
+        ui.label(t("Convenção para a idade em anos:", lang));
+        for convention in [AgeConvention::International, AgeConvention::Korean] {
+            changed |= ui
+                .radio_value(
+                    &mut self.settings.age_convention,
+                    convention,
+                    t(convention.label(), lang),
+                )
+                .changed();
+        }
+        ui.small(t(
+            "A contagem coreana considera 1 ano ao nascer e soma um ano a cada 1º de janeiro.",
+            lang,
+        ));
+
+        ui.separator();
+        ui.label(t("Meses totais (\"12 semanas (2 meses)\"):", lang));
+        for month_count in [MonthCount::Calendar, MonthCount::AverageDays] {
+            changed |= ui
+                .radio_value(
+                    &mut self.settings.month_count,
+                    month_count,
+                    t(month_count.label(), lang),
+                )
+                .changed();
+        }
+        ui.small(t(
+            "Os meses de calendário coincidem com a idade em anos, meses e dias; a média \
+             pode dar um mês a menos perto do \"mesversário\".",
+            lang,
+        ));
+
+        ui.separator();
+        changed |= ui
+            .checkbox(
+                &mut self.settings.show_formulas,
+                t("Mostrar as fórmulas de cálculo (modo didático)", lang),
+            )
+            .changed();
+
+        changed |= ui
+            .add_enabled(
+                self.settings.display_mode == DisplayMode::Parent,
+                egui::Checkbox::new(
+                    &mut self.settings.parent_week_fractions,
+                    t(
+                        "No modo para os pais, arredondar os dias (\"8 semanas e meia\")",
+                        lang,
+                    ),
+                ),
+            )
+            .changed();
+        changed |= ui
+            .checkbox(
+                &mut self.settings.feeding_reference,
+                t(
+                    "Mostrar o volume hídrico de referência no primeiro mês (requer o peso)",
+                    lang,
+                ),
+            )
+            .changed();
+
+        ui.separator();
+        ui.label(t("Tecla Enter nos campos:", lang));
+        for behavior in [EnterBehavior::NextField, EnterBehavior::Calculate] {
+            changed |= ui
+                .radio_value(
+                    &mut self.settings.enter_behavior,
+                    behavior,
+                    t(behavior.label(), lang),
+                )
+                .changed();
+        }
+        changed |= ui
+            .checkbox(
+                &mut self.settings.cursor_at_end_on_focus,
+                t(
+                    "Ao entrar em um campo, manter o cursor no fim (sem selecionar o texto)",
+                    lang,
+                ),
+            )
+            .changed();
+        changed |= ui
+            .checkbox(
+                &mut self.settings.decimal_gestational_weeks,
+                t("Aceitar semanas decimais (ex.: 32,5)", lang),
+            )
+            .on_hover_text(t(
+                "A fração é convertida no número de dias mais próximo.",
+                lang,
+            ))
+            .changed();
+        changed |= ui
+            .checkbox(
+                &mut self.settings.flash_on_calculate,
+                t("Destacar a área de resultados ao calcular", lang),
+            )
+            .changed();
+        // O som só existe quando o programa é compilado com a feature 'sound'.
+        if cfg!(feature = "sound") {
+            changed |= ui
+                .checkbox(
+                    &mut self.settings.sound_on_calculate,
+                    t("Tocar um som ao calcular", lang),
+                )
+                .changed();
+        }
+        changed |= ui
+            .checkbox(
+                &mut self.settings.age_in_taskbar,
+                t(
+                    "Com a janela minimizada, mostrar a idade corrigida no título",
+                    lang,
+                ),
+            )
+            .on_hover_text(t(
+                "O título aparece ao passar o mouse sobre o ícone na barra de tarefas e é \
+                 atualizado a cada dia.",
+                lang,
+            ))
+            .changed();
+        // O ícone na bandeja depende da feature 'tray' e do sistema (veja 'tray.rs').
+        changed |= ui
+            .add_enabled(
+                tray::SUPPORTED,
+                egui::Checkbox::new(
+                    &mut self.settings.tray_icon,
+                    t(
+                        "Mostrar a idade corrigida em um ícone na bandeja do sistema",
+                        lang,
+                    ),
+                ),
+            )
+            .on_hover_text(t(
+                "A idade corrigida de hoje do último cálculo aparece ao passar o mouse \
+                 sobre o ícone e é atualizada a cada dia.",
+                lang,
+            ))
+            .on_disabled_hover_text(t(
+                "Indisponível nesta versão: o ícone na bandeja exige a feature 'tray' e o \
+                 Windows ou o macOS.",
+                lang,
+            ))
+            .changed();
+        changed |= ui
+            .checkbox(
+                &mut self.settings.monochrome_export,
+                t("Exportar o PDF em preto e branco", lang),
+            )
+            .on_hover_text(t(
+                "Para impressoras monocromáticas: os destaques ficam em negrito com um marcador, em vez de coloridos.",
+                lang,
+            ))
+            .changed();
+        changed |= ui
+            .checkbox(
+                &mut self.settings.clear_after_export,
+                t("Limpar o formulário após exportar", lang),
+            )
+            .on_hover_text(t(
+                "Para digitar vários bebês em sequência. A limpeza pode ser desfeita.",
+                lang,
+            ))
+            .changed();
+
+        ui.separator();
+        changed |= ui
+            .checkbox(
+                &mut self.settings.snap_to_clinic_days,
+                t("Marcar as consultas em dias de atendimento", lang),
+            )
+            .on_hover_text(t(
+                "Move cada data para o dia de atendimento mais próximo.",
+                lang,
+            ))
+            .changed();
+        ui.add_enabled_ui(self.settings.snap_to_clinic_days, |ui| {
+            ui.horizontal(|ui| {
+                for (open, label) in self
+                    .settings
+                    .clinic_days
+                    .0
+                    .iter_mut()
+                    .zip(ClinicDays::LABELS)
+                {
+                    changed |= ui.checkbox(open, t(label, lang)).changed();
+                }
+            });
+        });
+
+        ui.separator();
+        egui::CollapsingHeader::new(t("Avançado", lang)).show(ui, |ui| {
+            ui.label(t("Corrigir a prematuridade para:", lang));
+            for term in [
+                CorrectionTerm::Weeks40,
+                CorrectionTerm::Weeks38,
+                CorrectionTerm::Weeks37,
+            ] {
+                changed |= ui
+                    .radio_value(
+                        &mut self.settings.correction_term,
+                        term,
+                        t(term.label(), lang),
+                    )
+                    .changed();
+            }
+            ui.small(t(
+                "Siga o protocolo do serviço. A data provável do parto continua sendo \
+                 calculada para 40 semanas.",
+                lang,
+            ));
+            ui.label(t(
+                "Fonte da idade gestacional, quando a DUM é informada:",
+                lang,
+            ));
+            for priority in [
+                GestationalAgePriority::InformedFirst,
+                GestationalAgePriority::LmpFirst,
+            ] {
+                changed |= ui
+                    .radio_value(
+                        &mut self.settings.gestational_age_priority,
+                        priority,
+                        t(priority.label(), lang),
+                    )
+                    .changed();
+            }
+            ui.small(t(
+                "O cálculo usa a primeira fonte válida; a usada aparece nos detalhes do \
+                 cálculo.",
+                lang,
+            ));
+            changed |= ui
+                .checkbox(
+                    &mut self.settings.show_default_term,
+                    t(
+                        "Indicar o termo de referência também quando for 40 semanas",
+                        lang,
+                    ),
+                )
+                .changed();
+        });
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            if ui.button(t("Importar...", lang)).clicked() {
+                if let Some(path) = file_dialog::FileDialog::new()
+                    .add_filter("JSON", &["json"])
+                    .pick_file()
+                {
+                    match Settings::import(&path) {
+                        Ok((settings, rejected)) => {
+                            self.settings = settings;
+                            changed = true;
+                            self.settings_message = Some(if rejected.is_empty() {
+                                t("Configurações importadas.", lang).to_string()
+                            } else {
+                                tf(
+                                    "Configurações importadas. Campos inválidos ignorados: {}.",
+                                    lang,
+                                    &[&rejected.join(", ")],
+                                )
+                            });
+                        }
+                        Err(e) => {
+                            self.settings_message = Some(tf("Falha ao importar: {}", lang, &[&e]));
+                        }
+                    }
+                }
+            }
+            if ui.button(t("Exportar...", lang)).clicked() {
+                if let Some(path) = file_dialog::FileDialog::new()
+                    .add_filter("JSON", &["json"])
+                    .set_file_name("configuracoes.json")
+                    .save_file()
+                {
+                    self.settings_message = Some(match self.settings.export(&path) {
+                        Ok(()) => t("Configurações exportadas.", lang).to_string(),
+                        Err(e) => tf("Falha ao exportar: {}", lang, &[&e]),
+                    });
+                }
+            }
+        });
+        if let Some(message) = &self.settings_message {
+            ui.label(message);
+        }
+
+        if changed {
+            self.apply_settings();
+        }
+    }
+}
+
+/// Implementa a lógica de atualização da interface gráfica.
+impl eframe::App for AgeCalculatorApp {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        let lang = self.settings.language;
+        let weeks_help = tf(WEEKS_HELP, lang, &[&self.settings.correction_term.weeks()]);
+
+        // Com o primeiro resultado na tela, procura de novo a área de transferência
+        // antes de decidir se os botões de copiar ficam ativos.
+        if self.report.is_some() {
+            self.retry_clipboard();
+        }
+
+        // Aplica a escala da interface. Se ela mudou por fora, pelos atalhos de zoom
+        // do egui (Ctrl + '+' e Ctrl + '-'), o novo valor é adotado e salvo, limitado à
+        // faixa da configuração (os atalhos do egui vão além dela).
+        let zoom = ctx.zoom_factor();
+        if self.applied_zoom.is_some_and(|applied| applied != zoom) {
+            self.settings.ui_scale = UiScale(zoom.clamp(UiScale::MIN, UiScale::MAX));
+            self.apply_settings();
+        }
+        let scale = self.settings.ui_scale.value();
+        if zoom != scale {
+            ctx.set_zoom_factor(scale);
+        }
+        self.applied_zoom = Some(scale);
+
+        // Aplica o tema escolhido. Para seguir o sistema, usa o tema informado pelo
+        // eframe; se ele não for conhecido, mantém o tema atual.
+        let dark_mode = match self.settings.theme {
+            Theme::Light => Some(false),
+            Theme::Dark => Some(true),
+            Theme::System => frame
+                .info()
+                .system_theme
+                .map(|theme| theme == eframe::Theme::Dark),
+        };
+        if let Some(dark_mode) = dark_mode.filter(|&dark| dark != ctx.style().visuals.dark_mode) {
+            ctx.set_visuals(if dark_mode {
+                egui::Visuals::dark()
+            } else {
+                egui::Visuals::light()
+            });
+        }
+
+        // Painel do rodapé com hyperlink.
+        egui::TopBottomPanel::bottom("footer_panel").show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(5.0);
+                ui.hyperlink_to(
+                    t("Desenvolvido em Rust por Paulo A V Munhoz", lang),
+                    "https://www.linkedin.com/in/paulomunhoz/",
+                );
+                ui.add_space(5.0);
+            });
+        });
+
+        // Com a janela aberta por dias (ex.: em um quiosque), recalcula quando a data
+        // muda, para que as idades exibidas continuem atuais. O repaint periódico
+        // garante que isso aconteça mesmo sem interação do usuário. Com uma data de
+        // referência fixa, não há o que atualizar.
+        let today = Utc::now().date_naive();
+        if self.reference_date_str.trim().is_empty()
+            && self
+                .report
+                .as_ref()
+                .is_some_and(|report| report.today != today)
+        {
+            self.calculate();
+        }
+        ctx.request_repaint_after(REFRESH_INTERVAL);
+
+        // Com a janela minimizada, o título (exibido ao passar o mouse sobre o ícone
+        // na barra de tarefas) mostra a idade corrigida de hoje do último cálculo,
+        // atualizada pelo mesmo repaint periódico.
+        let minimized = ctx.input(|i| i.viewport().minimized).unwrap_or(false);
+        let glance = self.report.as_ref().map(|report| {
+            glance_title(
+                report,
+                Some(self.patient_name.trim()).filter(|name| !name.is_empty()),
+                today,
+                lang,
+            )
+        });
+        let app_title = t("Calculadora de Idade Gestacional do Bebê", lang);
+        let title = match &glance {
+            Some(glance) if minimized && self.settings.age_in_taskbar => glance.clone(),
+            _ => app_title.to_string(),
+        };
+        if title != self.window_title {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+            self.window_title = title;
+        }
+        // O ícone na bandeja, quando ativado, mostra a mesma idade ao passar o mouse.
+        self.update_tray(glance.as_deref().unwrap_or(app_title));
+
+        // Atalho Ctrl+Z para desfazer a última ação destrutiva. Com um campo de texto
+        // em foco, o atalho fica com o campo, que desfaz a própria digitação.
+        let undo_shortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Z);
+        if ctx.memory(|m| m.focused().is_none())
+            && ctx.input_mut(|i| i.consume_shortcut(&undo_shortcut))
+        {
+            self.undo();
+        }
+
+        // Atalho Ctrl+C para copiar o resultado, como o botão "Copiar Resultado". O
+        // egui entrega o Ctrl+C como um evento de cópia, e não como uma tecla; com
+        // um campo de texto em foco, o atalho fica com o campo.
+        if ctx.memory(|m| m.focused().is_none())
+            && ctx.input(|i| i.events.iter().any(|event| event == &egui::Event::Copy))
+        {
+            self.copy_result_text();
+        }
+
+        // Atalho Ctrl+L para limpar o formulário, como o botão "Limpar".
+        if ctx.input_mut(|i| i.consume_shortcut(&CLEAR_SHORTCUT)) {
+            self.clear_form();
+        }
+
+        // Atalho Ctrl+M para alternar entre os modos clínico e para os pais.
+        let toggle_mode_shortcut =
+            egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::M);
+        if ctx.input_mut(|i| i.consume_shortcut(&toggle_mode_shortcut)) {
+            self.set_display_mode(self.settings.display_mode.next());
+        }
+
+        // Barra de menus no topo da janela.
+        egui::TopBottomPanel::top("menu_panel").show(ctx, |ui| {
+            egui::menu::bar(ui, |ui| {
+                ui.menu_button(t("Opções", lang), |ui| {
+                    if ui.button(t("Configurações...", lang)).clicked() {
+                        self.show_settings = true;
+                        ui.close_menu();
+                    }
+                    if ui
+                        .button(t("Comparar datas de referência...", lang))
+                        .clicked()
+                    {
+                        self.show_reference_dates = true;
+                        ui.close_menu();
+                    }
+                    if ui.button(t("Histórico de cálculos...", lang)).clicked() {
+                        self.show_history = true;
+                        ui.close_menu();
+                    }
+                });
+                ui.menu_button(t("Ajuda", lang), |ui| {
+                    if ui.button(t("Sobre...", lang)).clicked() {
+                        self.show_about = true;
+                        ui.close_menu();
+                    }
+                });
+                ui.menu_button(t("Exibição", lang), |ui| {
+                    for mode in [
+                        DisplayMode::Clinician,
+                        DisplayMode::Parent,
+                        DisplayMode::Complete,
+                    ] {
+                        let selected = self.settings.display_mode == mode;
+                        if ui
+                            .radio(selected, t(mode.label(), lang))
+                            .on_hover_text(t("Alterne com Ctrl+M", lang))
+                            .clicked()
+                        {
+                            self.set_display_mode(mode);
+                            ui.close_menu();
+                        }
+                    }
+                    ui.separator();
+                    for theme in [Theme::System, Theme::Light, Theme::Dark] {
+                        if ui
+                            .radio(self.settings.theme == theme, t(theme.label(), lang))
+                            .clicked()
+                        {
+                            self.settings.theme = theme;
+                            self.apply_settings();
+                            ui.close_menu();
+                        }
+                    }
+                    ui.separator();
+                    // O menu fica aberto para permitir vários cliques seguidos.
+                    ui.horizontal(|ui| {
+                        let scale = self.settings.ui_scale;
+                        ui.label(t("Tamanho do texto:", lang));
+                        if ui
+                            .add_enabled(scale.value() > UiScale::MIN, egui::Button::new("−"))
+                            .clicked()
+                        {
+                            self.settings.ui_scale = scale.step(-1);
+                            self.apply_settings();
+                        }
+                        ui.label(format!("{:.0}%", scale.value() * 100.0));
+                        if ui
+                            .add_enabled(scale.value() < UiScale::MAX, egui::Button::new("+"))
+                            .clicked()
+                        {
+                            self.settings.ui_scale = scale.step(1);
+                            self.apply_settings();
+                        }
+                    });
+                });
+                // Idioma da interface, à direita da barra. Os nomes dos idiomas ficam
+                // sempre no próprio idioma, para que cada um reconheça o seu.
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    egui::ComboBox::from_id_source("language")
+                        .selected_text(self.settings.language.label())
+                        .show_ui(ui, |ui| {
+                            for language in Lang::ALL {
+                                if ui
+                                    .selectable_value(
+                                        &mut self.settings.language,
+                                        language,
+                                        language.label(),
+                                    )
+                                    .changed()
+                                {
+                                    self.apply_settings();
+                                }
+                            }
+                        });
+                });
+            });
+        });
+
+        // Janela de configurações.
+        let mut show_settings = self.show_settings;
+        egui::Window::new(t("Configurações", lang))
+            .open(&mut show_settings)
+            .collapsible(false)
+            .show(ctx, |ui| self.settings_ui(ui));
+        self.show_settings = show_settings;
+
+        // Janela do cadastro de pacientes.
+        let mut show_roster = self.show_roster;
+        egui::Window::new(t("Pacientes", lang))
+            .open(&mut show_roster)
+            .collapsible(false)
+            .show(ctx, |ui| self.roster_ui(ui));
+        self.show_roster = show_roster;
+
+        // Janela do painel com as idades de hoje de todos os pacientes.
+        let mut show_dashboard = self.show_dashboard;
+        egui::Window::new(t("Painel do Dia", lang))
+            .open(&mut show_dashboard)
+            .collapsible(false)
+            .show(ctx, |ui| self.dashboard_ui(ui));
+        self.show_dashboard = show_dashboard;
+
+        // Janela com as idades do bebê em várias datas de referência.
+        let mut show_reference_dates = self.show_reference_dates;
+        egui::Window::new(t("Comparar Datas", lang))
+            .open(&mut show_reference_dates)
+            .collapsible(false)
+            .show(ctx, |ui| self.reference_dates_ui(ui));
+        self.show_reference_dates = show_reference_dates;
+
+        // Janela com os cálculos anteriores da sessão.
+        let mut show_history = self.show_history;
+        egui::Window::new(t("Histórico", lang))
+            .open(&mut show_history)
+            .collapsible(false)
+            .show(ctx, |ui| self.history_ui(ui));
+        self.show_history = show_history;
+
+        // Janela com as versões do programa e das tabelas de referência.
+        let mut show_about = self.show_about;
+        egui::Window::new(t("Sobre", lang))
+            .open(&mut show_about)
+            .collapsible(false)
+            .show(ctx, |ui| self.about_ui(ui));
+        self.show_about = show_about;
+
+        // Painel central com rolagem, para que tudo continue acessível quando a
+        // janela for reduzida.
+        egui::CentralPanel::default().show(ctx, |ui| {
+            egui::ScrollArea::vertical()
+                .id_source("central_scroll")
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    // Título da aplicação.
+                    ui.vertical_centered(|ui| {
+                        ui.add_space(10.0);
+                        ui.heading(t("Calculadora de Idade Gestacional do Bebê", lang));
+                    });
+                    ui.add_space(15.0);
+
+                    // IDs para os campos de entrada, para controle de foco.
+                    let birth_date_id = ui.id().with("birth_date_input");
+                    let weeks_id = ui.id().with("weeks_input");
+                    let days_id = ui.id().with("days_input");
+                    let lmp_id = ui.id().with("lmp_input");
+                    let reference_date_id = ui.id().with("reference_date_input");
+                    let mut birth_date_response = None;
+                    let mut weeks_response = None;
+                    let mut days_response = None;
+                    let mut lmp_response = None;
+                    let mut observer_response = None;
+                    let mut reference_date_response = None;
+                    let mut reset_to_term = false;
+
+                    // Grid para alinhar os rótulos e campos de entrada.
+                    ui.vertical_centered(|ui| {
+                        egui::Grid::new("input_grid")
+                            .num_columns(2)
+                            .spacing([10.0, 12.0])
+                            .show(ui, |ui| {
+                                // Campo para a data de nascimento, com um calendário ao lado para
+                                // quem prefere não digitar a data.
+                                ui.label(t("Data de Nascimento (DD/MM/AAAA):", lang))
+                                    .on_hover_text(t("Dia em que o bebê nasceu.", lang));
+                                ui.horizontal(|ui| {
+                                    birth_date_response = Some(
+                                        ui.add(
+                                            egui::TextEdit::singleline(&mut self.birth_date_str)
+                                                .id(birth_date_id),
+                                        ),
+                                    );
+                                    date_picker::date_picker_button(
+                                        ui,
+                                        "birth_date_picker",
+                                        &mut self.birth_date_str,
+                                        Utc::now().date_naive(),
+                                        lang,
+                                    );
+                                });
+                                ui.end_row();
+
+                                // Gestação múltipla: os outros bebês compartilham a idade
+                                // gestacional e só precisam da data, se for diferente.
+                                ui.label("");
+                                ui.horizontal(|ui| {
+                                    let mut twins = self.babies > 1;
+                                    if ui
+                                        .checkbox(&mut twins, t("Gêmeos", lang))
+                                        .on_hover_text(t(
+                                            "Calcula para os outros bebês com a mesma idade gestacional.",
+                                            lang,
+                                        ))
+                                        .changed()
+                                    {
+                                        self.babies = if twins { 2 } else { 1 };
+                                    }
+                                    if twins {
+                                        let mut triplets = self.babies == 3;
+                                        if ui.checkbox(&mut triplets, t("Trigêmeos", lang)).changed() {
+                                            self.babies = if triplets { 3 } else { 2 };
+                                        }
+                                    }
+                                });
+                                ui.end_row();
+                                for (index, date_str) in self.sibling_birth_dates_str[..self.babies - 1]
+                                    .iter_mut()
+                                    .enumerate()
+                                {
+                                    ui.label(tf("Nascimento do Bebê {}:", lang, &[&(index + 2)]));
+                                    ui.add(
+                                        egui::TextEdit::singleline(date_str)
+                                            .hint_text(t("mesma do Bebê 1", lang)),
+                                    );
+                                    ui.end_row();
+                                }
+
+                                // Campo para as semanas gestacionais.
+                                // O botão de passo ao lado ajusta o número sem digitar.
+                                ui.label(t("Idade Gestacional (semanas):", lang))
+                                    .on_hover_text(&weeks_help);
+                                ui.horizontal(|ui| {
+                                    weeks_response = Some(
+                                        ui.add(
+                                            egui::TextEdit::singleline(&mut self.gestational_weeks_str)
+                                                .id(weeks_id),
+                                        )
+                                        .on_hover_text(format!(
+                                            "{}\n{}",
+                                            weeks_help,
+                                            t(
+                                                "Com datação incerta, informe um intervalo (ex.: 31-33).",
+                                                lang,
+                                            )
+                                        )),
+                                    );
+                                    stepper(
+                                        ui,
+                                        &mut self.gestational_weeks_value,
+                                        &mut self.gestational_weeks_str,
+                                        MIN_GESTATIONAL_WEEKS..=MAX_GESTATIONAL_WEEKS,
+                                    );
+                                });
+                                ui.end_row();
+
+                                // Campo para os dias na semana de nascimento.
+                                ui.label(t("Dias na Semana de Nascimento:", lang))
+                                    .on_hover_text(t(DAYS_HELP, lang));
+                                // Vazio vale 0 dias; a dica mostra isso sem preencher o campo.
+                                ui.horizontal(|ui| {
+                                    days_response = Some(
+                                        ui.add(
+                                            egui::TextEdit::singleline(&mut self.gestational_days_str)
+                                                .id(days_id)
+                                                .hint_text("0"),
+                                        )
+                                        .on_hover_text(format!(
+                                            "{}\n{}",
+                                            t(DAYS_HELP, lang),
+                                            t("Vazio, conta como 0 dias.", lang)
+                                        )),
+                                    );
+                                    stepper(
+                                        ui,
+                                        &mut self.gestational_days_value,
+                                        &mut self.gestational_days_str,
+                                        0..=6,
+                                    );
+                                });
+                                ui.end_row();
+
+                                // Termo de referência da correção, para que uma idade
+                                // corrigida para 37 ou 38 semanas não surpreenda.
+                                if let Some(indicator) = term_indicator(&self.settings) {
+                                    ui.label("");
+                                    ui.small(indicator).on_hover_text(t(
+                                        "Altere em Opções > Configurações > Avançado.",
+                                        lang,
+                                    ));
+                                    ui.end_row();
+                                }
+
+                                // Campo opcional para a data da última menstruação (DUM).
+                                ui.label(t("DUM (opcional):", lang));
+                                lmp_response = Some(
+                                    ui.add(egui::TextEdit::singleline(&mut self.lmp_str).id(lmp_id))
+                                        .on_hover_text(t(
+                                            "Data da última menstruação, usada para conferir a idade \
+                                             gestacional ou, conforme as configurações, \
+                                             como fonte dela.",
+                                            lang,
+                                        )),
+                                );
+                                ui.end_row();
+
+                                // Campo opcional para o observador ou a fonte da idade gestacional.
+                                ui.label(t("Observador/fonte (opcional):", lang));
+                                observer_response = Some(
+                                    ui.text_edit_singleline(&mut self.observer_str)
+                                        .on_hover_text(t(
+                                            "Quem mediu ou de onde vem a idade gestacional.",
+                                            lang,
+                                        )),
+                                );
+                                ui.end_row();
+
+                                // Peso atual e sexo, opcionais, para o percentil de Fenton.
+                                ui.label(t("Peso atual (g, opcional):", lang));
+                                ui.horizontal(|ui| {
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut self.weight_str)
+                                            .desired_width(70.0),
+                                    )
+                                    .on_hover_text(t(
+                                        "Com o sexo, mostra o percentil aproximado do peso nas \
+                                         curvas de Fenton.",
+                                        lang,
+                                    ));
+                                });
+                                ui.end_row();
+
+                                // Sexo, usado pelos cálculos que dependem dele (percentis).
+                                ui.label(t("Sexo:", lang));
+                                ui.horizontal(|ui| {
+                                    for sex in [Some(growth::Sex::Male), Some(growth::Sex::Female), None] {
+                                        ui.radio_value(&mut self.sex, sex, t(growth::Sex::label(sex), lang));
+                                    }
+                                });
+                                ui.end_row();
+
+                                // Campo opcional para calcular as idades em outra data.
+                                ui.label(t("Data de Referência (opcional):", lang));
+                                reference_date_response = Some(
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut self.reference_date_str)
+                                            .id(reference_date_id)
+                                            .hint_text(t("hoje", lang)),
+                                    )
+                                    .on_hover_text(t(
+                                        "Data em que as idades são calculadas, por exemplo a de uma \
+                                         consulta passada. Vazia, usa a data de hoje.",
+                                        lang,
+                                    )),
+                                );
+                                ui.end_row();
+
+                                // Atalhos que preenchem a data de referência; o campo continua
+                                // editável para qualquer outra data.
+                                ui.label("");
+                                ui.horizontal(|ui| {
+                                    for (label, days_before) in REFERENCE_DATE_SHORTCUTS {
+                                        if ui
+                                            .small_button(t(label, lang))
+                                            .on_hover_text(t(
+                                                "Preenche a data de referência a partir de hoje.",
+                                                lang,
+                                            ))
+                                            .clicked()
+                                        {
+                                            self.reference_date_str =
+                                                days_before_today(Utc::now().date_naive(), days_before);
+                                        }
+                                    }
+                                });
+                                ui.end_row();
+
+                                // Atalho para bebês a termo: 40 semanas e 0 dias (sem correção),
+                                // e botão para arrumar os dados colados antes de calcular.
+                                ui.label("");
+                                ui.horizontal(|ui| {
+                                    if ui
+                                        .small_button(t("Bebê a termo (40 semanas)", lang))
+                                        .on_hover_text(t("Preenche 40 semanas e 0 dias e recalcula.", lang))
+                                        .clicked()
+                                    {
+                                        self.gestational_weeks_str = "40".to_string();
+                                        self.gestational_days_str = "0".to_string();
+                                        reset_to_term = true;
+                                    }
+                                    if ui
+                                        .small_button(t("Normalizar", lang))
+                                        .on_hover_text(t(
+                                            "Remove espaços, completa as datas e separa idades \
+                                             gestacionais como \"32+4\".",
+                                            lang,
+                                        ))
+                                        .clicked()
+                                    {
+                                        self.normalize_inputs();
+                                    }
+                                });
+                                ui.end_row();
+                            });
+                    });
+                    if reset_to_term {
+                        self.calculate_and_confirm();
+                    }
+
+                    // Quando uma data só é válida com o dia e o mês trocados, sugere a troca,
+                    // que o usuário confirma com um clique.
+                    let today = Utc::now().date_naive();
+                    for (label, field) in [
+                        ("Data de nascimento", &mut self.birth_date_str),
+                        ("DUM", &mut self.lmp_str),
+                        ("Data de referência", &mut self.reference_date_str),
+                    ] {
+                        let Some(date) =
+                            dates::transposed_date(field, today, dates::system_date_order())
+                        else {
+                            continue;
+                        };
+                        let suggestion = date.format(dates::DATE_FORMAT).to_string();
+                        ui.horizontal(|ui| {
+                            ui.label(tf(
+                                "{}: dia e mês invertidos? Você quis dizer {}?",
+                                lang,
+                                &[&t(label, lang), &suggestion],
+                            ));
+                            if ui.small_button(t("Usar esta data", lang)).clicked() {
+                                *field = suggestion;
+                            }
+                        });
+                    }
+
+                    // Ao entrar em um campo, seleciona o texto para que a digitação o substitua
+                    // (a menos que o usuário prefira o cursor no fim).
+                    if !self.settings.cursor_at_end_on_focus {
+                        for (response, text) in [
+                            (&birth_date_response, &self.birth_date_str),
+                            (&weeks_response, &self.gestational_weeks_str),
+                            (&days_response, &self.gestational_days_str),
+                            (&lmp_response, &self.lmp_str),
+                            (&observer_response, &self.observer_str),
+                            (&reference_date_response, &self.reference_date_str),
+                        ] {
+                            if let Some(response) = response {
+                                select_all_on_focus(ctx, response, text);
+                            }
+                        }
+                    }
+
+                    // Ao pressionar 'Enter' em um campo, avança para o próximo ou calcula,
+                    // conforme a configuração (veja 'enter_action').
+                    if ctx.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        let fields = [
+                            (InputField::BirthDate, birth_date_response),
+                            (InputField::Weeks, weeks_response),
+                            (InputField::Days, days_response),
+                            (InputField::Lmp, lmp_response),
+                            (InputField::ReferenceDate, reference_date_response),
+                        ];
+                        let entered = fields.into_iter().find_map(|(field, response)| {
+                            response
+                                .is_some_and(|response| response.lost_focus())
+                                .then_some(field)
+                        });
+                        if let Some(field) = entered {
+                            let inputs_valid = self.parse_inputs().is_ok();
+                            match enter_action(field, self.settings.enter_behavior, inputs_valid) {
+                                EnterAction::Focus(next) => {
+                                    let id = match next {
+                                        InputField::BirthDate => birth_date_id,
+                                        InputField::Weeks => weeks_id,
+                                        InputField::Days => days_id,
+                                        InputField::Lmp => lmp_id,
+                                        InputField::ReferenceDate => reference_date_id,
+                                    };
+                                    ctx.memory_mut(|m| m.request_focus(id));
+                                }
+                                EnterAction::Calculate => self.calculate_and_confirm(),
+                            }
+                        }
+                    }
+
+                    ui.add_space(15.0);
+
+                    // Botões de "Calcular", "Limpar" e "Pacientes".
+                    // As linhas de botões quebram quando não cabem (com o texto ampliado).
+                    ui.vertical_centered(|ui| {
+                        ui.horizontal_wrapped(|ui| {
+                            let button_width = 100.0;
+                            let spacing = ui.spacing().item_spacing.x;
+                            let total_width = (button_width * 3.0) + spacing * 2.0;
+                            let left_space = (ui.available_width() - total_width).max(0.0) / 2.0;
+                            ui.add_space(left_space);
+
+                            if ui
+                                .add_sized([button_width, 30.0], egui::Button::new(t("Calcular", lang)))
+                                .clicked()
+                            {
+                                self.calculate_and_confirm();
+                            }
+                            if ui
+                                .add_sized([button_width, 30.0], egui::Button::new(t("Limpar", lang)))
+                                .on_hover_text(tf(
+                                    "Atalho: {}",
+                                    lang,
+                                    &[&ctx.format_shortcut(&CLEAR_SHORTCUT)],
+                                ))
+                                .clicked()
+                            {
+                                self.clear_form();
+                            }
+                            if ui
+                                .add_sized(
+                                    [button_width, 30.0],
+                                    egui::Button::new(t("Pacientes", lang)),
+                                )
+                                .clicked()
+                            {
+                                self.show_roster = !self.show_roster;
+                            }
+                        });
+                        if let Some(entry) = self.undo_stack.last() {
+                            if ui
+                                .small_button(t("Desfazer", lang))
+                                .on_hover_text(format!("{} (Ctrl+Z)", entry.description(lang)))
+                                .clicked()
+                            {
+                                self.undo();
+                            }
+                        }
+                    });
+
+                    ui.add_space(15.0);
+
+                    let clipboard_available = self.clipboard.is_some();
+                    let clipboard_unavailable = t(
+                        "Área de transferência não disponível (comum em sessões Wayland ou sem ambiente gráfico). Use a exportação em PDF ou selecione o texto.",
+                        lang,
+                    );
+
+                    // Exibe mensagens de erro ou os resultados, com rolagem quando não couberem.
+                    let results = egui::ScrollArea::vertical().show(ui, |ui| {
+                        ui.vertical_centered(|ui| {
+                            if let Some(message) = &self.normalize_message {
+                                ui.label(message);
+                            }
+                            if let Some(error) = &self.error_message {
+                                ui.colored_label(egui::Color32::RED, error);
+                            }
+                            if let Some(warning) = &self.warning_message {
+                                ui.colored_label(egui::Color32::from_rgb(230, 140, 0), warning);
+                            }
+                            if let Some(info) = &self.info_message {
+                                ui.label(egui::RichText::new(info).italics());
+                            }
+                            let Some(report) = &self.report else {
+                                return;
+                            };
+                            if report.today != Utc::now().date_naive() {
+                                ui.label(tf(
+                                    "Idades calculadas em {}.",
+                                    lang,
+                                    &[&report.today.format(dates::DATE_FORMAT)],
+                                ));
+                            }
+                            // Classificação da prematuridade, colorida do vermelho (mais grave)
+                            // ao verde (termo). O texto também indica a faixa, para não
+                            // depender só da cor.
+                            let category =
+                                preterm_category(report.post_menstrual_days - (report.day_of_life - 1));
+                            ui.label(
+                                egui::RichText::new(tf(
+                                    "Classificação: {}",
+                                    lang,
+                                    &[&t(category.label(), lang)],
+                                ))
+                                .strong()
+                                .color(category.color(ui.visuals().dark_mode)),
+                            );
+                            let mut dismiss_growth_notice = false;
+                            if let Some(weight) = weight_percentile_text(report, lang) {
+                                ui.label(weight).on_hover_text(t(
+                                    "Valor aproximado, interpolado das curvas de Fenton (2013) pela \
+                                     idade pós-menstrual. Confira no gráfico oficial.",
+                                    lang,
+                                ));
+                                // Aviso discreto, que não impede o uso, quando há curvas mais
+                                // novas que as embutidas.
+                                if let (Some(latest), false) =
+                                    (growth::newer_version(), self.growth_notice_dismissed)
+                                {
+                                    ui.horizontal(|ui| {
+                                        ui.small(tf(
+                                            "Percentil pelas curvas {}; já existe a versão {}.",
+                                            lang,
+                                            &[&growth::TABLE_VERSION, &latest],
+                                        ));
+                                        if ui
+                                            .small_button("✖")
+                                            .on_hover_text(t("Dispensar o aviso", lang))
+                                            .clicked()
+                                        {
+                                            dismiss_growth_notice = true;
+                                        }
+                                    });
+                                }
+                            }
+                            // Destaque para o próximo "mesversário" em idade corrigida.
+                            if let Some(birthday) = &report.next_month_birthday {
+                                let days_left =
+                                    birthday.date.signed_duration_since(report.today).num_days();
+                                let when = if days_left == 0 {
+                                    t("hoje!", lang).to_string()
+                                } else {
+                                    tf(
+                                        "em {} (faltam {} dias)",
+                                        lang,
+                                        &[&birthday.date.format("%d/%m/%Y"), &days_left],
+                                    )
+                                };
+                                ui.label(
+                                    egui::RichText::new(tf(
+                                        "Próximo mesversário corrigido: {} meses {}",
+                                        lang,
+                                        &[&birthday.corrected_months, &when],
+                                    ))
+                                    .strong()
+                                    .color(egui::Color32::from_rgb(40, 140, 200)),
+                                );
+                                ui.add_space(5.0);
+                            }
+                            // Aviso de que a correção pela prematuridade está perto do fim.
+                            if let Some(weeks_left) = weeks_until_cutoff(report) {
+                                ui.label(tf(
+                                    "Faltam {} semanas para a correção deixar de ser aplicada \
+                                     ({} meses corrigidos).",
+                                    lang,
+                                    &[&weeks_left, &CORRECTION_CUTOFF_MONTHS],
+                                ));
+                                ui.add_space(5.0);
+                            }
+                            // Campo de texto de múltiplas linhas para exibir o resultado. Com um
+                            // '&str' no lugar de uma 'String', o egui não permite editar o texto,
+                            // mas ele continua selecionável para copiar apenas um trecho.
+                            let result_text =
+                                format_babies_text(report, &self.sibling_reports, lang, |report| {
+                                    format_result_text(report, &self.settings)
+                                });
+                            ui.add(egui::TextEdit::multiline(&mut result_text.as_str()))
+                                .on_hover_text(tf(
+                                    CORRECTED_AGE_HELP,
+                                    lang,
+                                    &[&report.term_weeks],
+                                ));
+                            ui.add_space(10.0);
+                            // Métricas secundárias em uma seção recolhível, cujo estado é lembrado.
+                            // Cada linha tem um botão para copiar apenas aquele valor.
+                            let mut toggle_details = false;
+                            let mut value_to_copy = None;
+                            let details = egui::CollapsingHeader::new(t("Detalhes do cálculo", lang))
+                                .default_open(self.settings.details_expanded)
+                                .show(ui, |ui| {
+                                    let convention = self.settings.age_convention;
+                                    let mut rows = result_rows(report, convention, lang);
+                                    rows.extend(detail_rows(report, lang));
+                                    let mut formulas = result_formulas(report, convention, lang);
+                                    formulas.extend(detail_formulas(report, lang));
+                                    egui::Grid::new("details_grid")
+                                        .num_columns(3)
+                                        .striped(true)
+                                        .show(ui, |ui| {
+                                            for ((label, value), formula) in rows.into_iter().zip(formulas)
+                                            {
+                                                ui.label(&label);
+                                                ui.label(&value);
+                                                let copy_button = ui.add_enabled(
+                                                    clipboard_available,
+                                                    egui::Button::new("📋").small(),
+                                                );
+                                                if copy_button
+                                                    .on_hover_text(t("Copiar este valor", lang))
+                                                    .on_disabled_hover_text(clipboard_unavailable)
+                                                    .clicked()
+                                                {
+                                                    value_to_copy = Some(format!("{}: {}", label, value));
+                                                }
+                                                ui.end_row();
+                                                if self.settings.show_formulas {
+                                                    ui.label("");
+                                                    ui.small(formula);
+                                                    ui.end_row();
+                                                }
+                                            }
+                                        });
+                                });
+                            if details.header_response.clicked() {
+                                toggle_details = true;
+                            }
+                            // Painel didático: compara a idade corrigida com a idade que o bebê
+                            // teria se tivesse nascido a termo no mesmo dia.
+                            egui::CollapsingHeader::new(t("E se tivesse nascido a termo?", lang)).show(
+                                ui,
+                                |ui| {
+                                    ui.label(t(
+                                        "Um bebê a termo não precisa de correção: a idade corrigida \
+                                         seria igual à cronológica. A diferença é o tempo que faltou \
+                                         de gestação.",
+                                        lang,
+                                    ));
+                                    egui::Grid::new("what_if_grid")
+                                        .num_columns(2)
+                                        .striped(true)
+                                        .show(ui, |ui| {
+                                            for (label, value) in what_if_rows(report, lang) {
+                                                ui.label(label);
+                                                ui.label(value);
+                                                ui.end_row();
+                                            }
+                                        });
+                                },
+                            );
+                            // Tabela com as datas das consultas de seguimento.
+                            egui::CollapsingHeader::new(t(
+                                "Consultas de seguimento (idade corrigida)",
+                                lang,
+                            ))
+                            .show(ui, |ui| {
+                                egui::Grid::new("checkpoints_grid")
+                                    .num_columns(2)
+                                    .striped(true)
+                                    .show(ui, |ui| {
+                                        for checkpoint in scheduled_checkpoints(report, &self.settings) {
+                                            ui.label(tf(
+                                                "{} meses corrigidos",
+                                                lang,
+                                                &[&checkpoint.corrected_months],
+                                            ));
+                                            ui.label(checkpoint.date.format("%d/%m/%Y").to_string());
+                                            ui.end_row();
+                                        }
+                                    });
+                                // Marcos esperados em cada consulta, apenas como referência.
+                                egui::CollapsingHeader::new(t("Marcos do desenvolvimento esperados", lang))
+                                    .show(ui, |ui| {
+                                        for checkpoint in &report.checkpoints {
+                                            let Some(milestone) = milestones::for_corrected_months(
+                                                checkpoint.corrected_months,
+                                            ) else {
+                                                continue;
+                                            };
+                                            ui.label(
+                                                egui::RichText::new(tf(
+                                                    "{} meses corrigidos",
+                                                    lang,
+                                                    &[&milestone.corrected_months],
+                                                ))
+                                                .strong(),
+                                            );
+                                            ui.label(tf("Motor: {}", lang, &[&t(milestone.motor, lang)]));
+                                            ui.label(tf(
+                                                "Linguagem: {}",
+                                                lang,
+                                                &[&t(milestone.language, lang)],
+                                            ));
+                                        }
+                                        ui.small(t(
+                                            "Referência geral: cada criança tem seu ritmo. \
+                                                 Converse com o pediatra em caso de dúvida.",
+                                            lang,
+                                        ));
+                                    });
+                            });
+                            // Próximas vacinas do calendário do PNI, pela idade cronológica, com
+                            // a idade corrigida na data apenas como referência.
+                            egui::CollapsingHeader::new(t("Próximas vacinas (calendário do PNI)", lang))
+                                .show(ui, |ui| {
+                                    let upcoming = vaccines::upcoming(report.birthdate, report.today);
+                                    if upcoming.is_empty() {
+                                        ui.label(t("Nenhuma vacina prevista até os 15 meses.", lang));
+                                    }
+                                    for (due, milestone) in upcoming {
+                                        let corrected_days = due
+                                            .signed_duration_since(report.corrected_birthdate)
+                                            .num_days()
+                                            .max(0);
+                                        let age = if milestone.months == 0 {
+                                            t("Ao nascer", lang).to_string()
+                                        } else {
+                                            tf("{} meses", lang, &[&milestone.months])
+                                        };
+                                        ui.label(
+                                            egui::RichText::new(tf(
+                                                "{}: {} (idade corrigida: {} semanas)",
+                                                lang,
+                                                &[
+                                                    &age,
+                                                    &due.format(dates::DATE_FORMAT),
+                                                    &(corrected_days / 7),
+                                                ],
+                                            ))
+                                            .strong(),
+                                        );
+                                        ui.label(t(milestone.vaccines, lang));
+                                        if let Some(note) = milestone.note {
+                                            ui.small(t(note, lang));
+                                        }
+                                    }
+                                    ui.small(t(
+                                        "As vacinas seguem a idade cronológica, também nos prematuros. \
+                                         Confirme sempre com o calendário vigente e o pediatra.",
+                                        lang,
+                                    ));
+                                });
+                            // Volume hídrico de referência pelo peso e pelo dia de vida, opcional
+                            // e apenas no primeiro mês.
+                            if let (true, Some(grams)) =
+                                (self.settings.feeding_reference, report.weight_grams)
+                            {
+                                egui::CollapsingHeader::new(t("Volume hídrico de referência", lang))
+                                    .show(ui, |ui| {
+                                        match feeding::daily_volume(report.day_of_life, grams) {
+                                            Some(volume) => {
+                                                ui.label(tf(
+                                                    "{}º dia de vida: {} a {} mL/kg/dia",
+                                                    lang,
+                                                    &[
+                                                        &report.day_of_life,
+                                                        &volume.ml_per_kg.0,
+                                                        &volume.ml_per_kg.1,
+                                                    ],
+                                                ));
+                                                ui.label(
+                                                    egui::RichText::new(tf(
+                                                        "Com {} g: {} a {} mL por dia",
+                                                        lang,
+                                                        &[
+                                                            &grams,
+                                                            &volume.ml_per_day.0,
+                                                            &volume.ml_per_day.1,
+                                                        ],
+                                                    ))
+                                                    .strong(),
+                                                );
+                                                ui.label(tf(
+                                                    "{} a {} mL por mamada, em {} mamadas ao dia",
+                                                    lang,
+                                                    &[
+                                                        &volume.ml_per_feed.0,
+                                                        &volume.ml_per_feed.1,
+                                                        &feeding::FEEDS_PER_DAY,
+                                                    ],
+                                                ));
+                                            }
+                                            None => {
+                                                ui.label(tf(
+                                                    "A referência cobre apenas até o {}º dia de vida.",
+                                                    lang,
+                                                    &[&feeding::LAST_DAY],
+                                                ));
+                                            }
+                                        }
+                                        ui.small(t(
+                                            "Apenas referência, para recém-nascidos a termo: não \
+                                             substitui a prescrição. Prematuros e bebês com \
+                                             restrição hídrica seguem o protocolo da unidade.",
+                                            lang,
+                                        ));
+                                        ui.small(tf(
+                                            "Fonte: {}",
+                                            lang,
+                                            &[&t(feeding::SOURCE, lang)],
+                                        ));
+                                    });
+                            }
+                            let mut copy_dates = false;
+                            let mut export_clicked = false;
+                            ui.horizontal(|ui| {
+                                copy_dates = ui
+                                    .add_enabled(
+                                        clipboard_available,
+                                        egui::Button::new(t("Copiar todas as datas", lang)).small(),
+                                    )
+                                    .on_hover_text(t(
+                                        "Nascimento, termo, concepção e consultas, uma por linha.",
+                                        lang,
+                                    ))
+                                    .on_disabled_hover_text(clipboard_unavailable)
+                                    .clicked();
+                                egui::ComboBox::from_id_source("export_format")
+                                    .selected_text(t(self.export_format.label(), lang))
+                                    .show_ui(ui, |ui| {
+                                        for format in [
+                                            ExportFormat::Text,
+                                            ExportFormat::Fhir,
+                                            ExportFormat::Pdf,
+                                            ExportFormat::Csv,
+                                        ] {
+                                            ui.selectable_value(
+                                                &mut self.export_format,
+                                                format,
+                                                t(format.label(), lang),
+                                            );
+                                        }
+                                    });
+                                export_clicked = ui.small_button(t("Exportar...", lang)).clicked();
+                            });
+                            ui.add_space(10.0);
+                            // Botões para copiar o resultado como texto, como HTML ou para SMS.
+                            let mut copy_text = false;
+                            let mut copy_html = false;
+                            let mut copy_sms = false;
+                            ui.horizontal_wrapped(|ui| {
+                                let button_width = 130.0;
+                                let spacing = ui.spacing().item_spacing.x;
+                                let total_width = (button_width * 3.0) + spacing * 2.0;
+                                let left_space = (ui.available_width() - total_width).max(0.0) / 2.0;
+                                ui.add_space(left_space);
+
+                                // Sem área de transferência, os botões ficam desativados e a
+                                // dica explica o motivo, em vez de um erro após o clique.
+                                ui.add_enabled_ui(clipboard_available, |ui| {
+                                    copy_text = ui
+                                        .add_sized(
+                                            [button_width, 30.0],
+                                            egui::Button::new(t("Copiar Resultado", lang)),
+                                        )
+                                        .on_hover_text(tf(
+                                            "Atalho: {} (fora dos campos de texto)",
+                                            lang,
+                                            &[&ctx.format_shortcut(&COPY_SHORTCUT)],
+                                        ))
+                                        .on_disabled_hover_text(clipboard_unavailable)
+                                        .clicked();
+                                    copy_html = ui
+                                        .add_sized(
+                                            [button_width, 30.0],
+                                            egui::Button::new(t("Copiar como HTML", lang)),
+                                        )
+                                        .on_disabled_hover_text(clipboard_unavailable)
+                                        .clicked();
+                                    copy_sms = ui
+                                        .add_sized(
+                                            [button_width, 30.0],
+                                            egui::Button::new(t("Copiar para SMS", lang)),
+                                        )
+                                        .on_hover_text(t("Resumo de até 160 caracteres", lang))
+                                        .on_disabled_hover_text(clipboard_unavailable)
+                                        .clicked();
+                                });
+                            });
+                            let sms = copy_sms.then(|| format_sms_summary(report, lang));
+                            let all_dates = copy_dates.then(|| format_all_dates(report, &self.settings));
+                            let export = export_clicked.then(|| {
+                                let contents = export_contents(
+                                    self.export_format,
+                                    report,
+                                    &self.sibling_reports,
+                                    &self.settings,
+                                );
+                                (self.export_format, contents)
+                            });
+                            if copy_text {
+                                self.copy_result_text();
+                            }
+                            if copy_html {
+                                self.copy_result_html();
+                            }
+                            if let Some(sms) = sms {
+                                self.copy_text(sms);
+                            }
+                            if let Some(all_dates) = all_dates {
+                                self.copy_text(all_dates);
+                            }
+                            if let Some((format, contents)) = export {
+                                if let Some(path) = file_dialog::FileDialog::new()
+                                    .set_file_name(format.file_name())
+                                    .save_file()
+                                {
+                                    let written = if format == ExportFormat::Csv {
+                                        append_csv_row(&path, &contents)
+                                    } else {
+                                        std::fs::write(&path, contents)
+                                    };
+                                    match written {
+                                        Ok(()) if self.settings.clear_after_export => self.clear_form(),
+                                        Ok(()) => {}
+                                        Err(e) => {
+                                            self.error_message =
+                                                Some(tf("Falha ao exportar: {}", lang, &[&e]));
+                                        }
+                                    }
+                                }
+                            }
+                            if let Some(text) = value_to_copy {
+                                self.copy_text(text);
+                            }
+                            if toggle_details {
+                                self.settings.details_expanded = !self.settings.details_expanded;
+                                self.apply_settings();
+                            }
+                            if dismiss_growth_notice {
+                                self.growth_notice_dismissed = true;
+                            }
+                        });
+                    });
+
+                    // Lampejo sobre a área de resultados: a cor surge e some suavemente. A
+                    // animação sobe após o cálculo e, ao atingir o máximo, volta a zero.
+                    let intensity =
+                        ctx.animate_bool_with_time(egui::Id::new("result_flash"), self.flash_rising, 0.2);
+                    if intensity >= 1.0 {
+                        self.flash_rising = false;
+                    }
+                    if let Some(cue) = self.flash_cue.filter(|_| intensity > 0.0) {
+                        ui.painter().rect_filled(
+                            results.inner_rect,
+                            4.0,
+                            cue.color().gamma_multiply(0.3 * intensity),
+                        );
+                    }
+                });
+        });
+    }
+
+    /// Ao fechar, grava os campos principais para a próxima sessão. Uma falha aqui
+    /// não tem onde ser exibida e apenas faz a próxima sessão começar vazia.
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        let _ = self.last_inputs().save();
+    }
+}
+
+/// Botão de passo (arrastar ou clicar e digitar) ao lado de um campo numérico. O
+/// valor acompanha o número digitado no campo e, quando é alterado pelo botão,
+/// reescreve o campo; textos que não são um número inteiro (como "32+4" ou
+/// "31-33") ficam como estão até o botão ser usado.
+fn stepper(
+    ui: &mut egui::Ui,
+    value: &mut i32,
+    text: &mut String,
+    range: std::ops::RangeInclusive<i32>,
+) {
+    if let Ok(typed) = i32::from_str(text.trim()) {
+        *value = typed.clamp(*range.start(), *range.end());
+    }
+    if ui
+        .add(egui::DragValue::new(value).clamp_range(range).speed(0.1))
+        .changed()
+    {
+        *text = value.to_string();
+    }
+}
+
+/// Seleciona todo o texto de um campo que acabou de receber o foco, pelo estado
+/// do 'TextEdit' guardado pelo egui.
+fn select_all_on_focus(ctx: &egui::Context, response: &egui::Response, text: &str) {
+    if !response.gained_focus() || text.is_empty() {
+        return;
+    }
+    if let Some(mut state) = egui::TextEdit::load_state(ctx, response.id) {
+        state
+            .cursor
+            .set_char_range(Some(egui::text::CCursorRange::two(
+                egui::text::CCursor::new(0),
+                egui::text::CCursor::new(text.chars().count()),
+            )));
+        state.store(ctx, response.id);
+    }
+}
+
+/// Registros sem identificação de todos os pacientes, com as idades em `today`.
+fn deidentified_records(
+    patients: &[Patient],
+    today: NaiveDate,
+    session_key: &std::hash::RandomState,
+    term_weeks: i32,
+) -> Vec<DeidentifiedRecord> {
+    patients
+        .iter()
+        .map(|patient| {
+            let report = compute_all(
+                patient.birthdate,
+                patient.gestational_weeks,
+                patient.gestational_days,
+                today,
+                // Os registros exportados têm as idades em dias, e não em meses.
+                MonthCount::default(),
+                term_weeks,
+            );
+            DeidentifiedRecord {
+                id: patient.anonymous_id(session_key),
+                gestational_weeks: patient.gestational_weeks,
+                gestational_days: patient.gestational_days,
+                chronological_age_days: report.day_of_life - 1,
+                corrected_age_days: report.corrected.weeks * 7 + report.corrected.days_in_week,
+                post_menstrual_age_days: report.post_menstrual_days,
+            }
+        })
+        .collect()
+}
+
+/// Separa as datas de referência digitadas, retornando as válidas (em ordem
+/// cronológica, sem repetições) e os trechos que não puderam ser interpretados.
+fn parse_reference_dates(input: &str, today: NaiveDate) -> (Vec<NaiveDate>, Vec<String>) {
+    let mut valid = Vec::new();
+    let mut invalid = Vec::new();
+    for entry in input
+        .split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+        .filter(|entry| !entry.is_empty())
+    {
+        match dates::parse_date(entry, today) {
+            Some(date) => valid.push(date),
+            None => invalid.push(entry.to_string()),
+        }
+    }
+    valid.sort();
+    valid.dedup();
+    (valid, invalid)
+}
+
+/// Células da tabela de datas de referência: data, idade cronológica e corrigida.
+fn reference_grid_cells(rows: &[Report], lang: Lang) -> Vec<(String, String, String)> {
+    rows.iter()
+        .map(|report| {
+            (
+                report.today.format(dates::DATE_FORMAT).to_string(),
+                tf(
+                    "{} semanas e {} dias",
+                    lang,
+                    &[
+                        &report.chronological.total_weeks,
+                        &((report.day_of_life - 1) % 7),
+                    ],
+                ),
+                tf(
+                    "{} semanas e {} dias",
+                    lang,
+                    &[&report.corrected.weeks, &report.corrected.days_in_week],
+                ),
+            )
+        })
+        .collect()
+}
+
+/// Formata a tabela de datas de referência como texto separado por tabulações,
+/// para colar em planilhas.
+fn format_reference_grid(rows: &[Report], lang: Lang) -> String {
+    let mut text = format!(
+        "{}\t{}\t{}",
+        t("Data", lang),
+        t("Idade Cronológica", lang),
+        t("Idade Corrigida", lang)
+    );
+    for (date, chronological, corrected) in reference_grid_cells(rows, lang) {
+        text.push_str(&format!("\n{}\t{}\t{}", date, chronological, corrected));
+    }
+    text
+}
+
+/// Calcula as idades de hoje de todos os pacientes, ordenadas pelo critério escolhido.
+fn dashboard_rows<'a>(
+    patients: &'a [Patient],
+    today: NaiveDate,
+    sort: &DashboardSort,
+    month_count: MonthCount,
+    term_weeks: i32,
+) -> Vec<(&'a Patient, Report)> {
+    let mut rows: Vec<(&Patient, Report)> = patients
+        .iter()
+        .map(|patient| {
+            let report = compute_all(
+                patient.birthdate,
+                patient.gestational_weeks,
+                patient.gestational_days,
+                today,
+                month_count,
+                term_weeks,
+            );
+            (patient, report)
+        })
+        .collect();
+    match sort {
+        DashboardSort::Name => {
+            rows.sort_by_key(|(patient, _)| patient.name.to_lowercase());
+        }
+        DashboardSort::CorrectedAge => rows
+            .sort_by_key(|(_, report)| report.corrected.weeks * 7 + report.corrected.days_in_week),
+    }
+    rows
+}
+
+/// Formata o resumo semanal com as idades atuais de todos os pacientes, pronto
+/// para ser colado em um e-mail ou mensagem.
+fn format_weekly_digest(rows: &[(&Patient, Report)], today: NaiveDate, lang: Lang) -> String {
+    let week_start = today - chrono::Duration::days(6);
+    let mut digest = tf(
+        "Resumo semanal de idades ({} a {})\n{} paciente(s)\n",
+        lang,
+        &[
+            &week_start.format("%d/%m/%Y"),
+            &today.format("%d/%m/%Y"),
+            &rows.len(),
+        ],
+    );
+    for (patient, report) in rows {
+        digest.push_str(&format!("\n{}\n", patient.name));
+        digest.push_str(&tf(
+            "  Nascimento: {} ({} semanas e {} dias de gestação)\n  Idade Cronológica: {} semanas ({} meses)\n  Idade Corrigida: {} semanas e {} dias\n",
+            lang,
+            &[
+                &patient.birthdate.format("%d/%m/%Y"),
+                &patient.gestational_weeks,
+                &patient.gestational_days,
+                &report.chronological.total_weeks,
+                &report.chronological.total_months,
+                &report.corrected.weeks,
+                &report.corrected.days_in_week,
+            ],
+        ));
+        if let Some(birthday) = &report.next_month_birthday {
+            digest.push_str(&tf(
+                "  Próximo mesversário corrigido: {} meses em {}\n",
+                lang,
+                &[
+                    &birthday.corrected_months,
+                    &birthday.date.format("%d/%m/%Y"),
+                ],
+            ));
+        }
+    }
+    digest
+}
+
+/// Linhas do resultado (rótulo e valor), compartilhadas pelos formatos de
+/// texto simples e HTML.
+fn result_rows(report: &Report, convention: AgeConvention, lang: Lang) -> Vec<(String, String)> {
+    let chronological_age = &report.chronological;
+    let corrected_age = &report.corrected;
+    let mut rows = vec![
+        (
+            t("Idade Cronológica", lang).to_string(),
+            tf(
+                "{} semanas ({} meses) e {} dias",
+                lang,
+                &[
+                    &chronological_age.total_weeks,
+                    &chronological_age.total_months,
+                    &chronological_age.days_in_week,
+                ],
+            ),
+        ),
+        (
+            t("Idade Corrigida", lang).to_string(),
+            tf(
+                "{} semanas ({} meses) e {} dias",
+                lang,
+                &[
+                    &corrected_age.weeks,
+                    &corrected_age.total_months,
+                    &corrected_age.days_in_week,
+                ],
+            ),
+        ),
+    ];
+
+    rows.push((
+        t("Idade Corrigida (meses e dias)", lang).to_string(),
+        format_corrected_months_and_days(report, lang),
+    ));
+
+    // Com datação incerta, mostra a idade corrigida nos dois extremos do intervalo.
+    if let Some((earliest, latest)) = &report.corrected_range {
+        rows.push((
+            t("Idade Corrigida (intervalo de IG)", lang).to_string(),
+            tf("{}–{} semanas", lang, &[&earliest.weeks, &latest.weeks]),
+        ));
+    }
+
+    // Idades em semanas decimais, a forma usada nos trabalhos de pesquisa.
+    rows.push((
+        t("Idades em Semanas Decimais", lang).to_string(),
+        tf(
+            "cronológica {}; corrigida {}",
+            lang,
+            &[
+                &format_decimal_weeks(report.day_of_life - 1, lang),
+                &format_decimal_weeks(corrected_age.weeks * 7 + corrected_age.days_in_week, lang),
+            ],
+        ),
+    ));
+
+    // Idade pós-menstrual, a medida de maturidade usada nos prontuários de UTI neonatal.
+    rows.push((
+        t("Idade Pós-Menstrual", lang).to_string(),
+        tf(
+            "{} semanas e {} dias",
+            lang,
+            &[
+                &(report.post_menstrual_days / 7),
+                &(report.post_menstrual_days % 7),
+            ],
+        ),
+    ));
+
+    // Para bebês a termo, a DPP cai no nascimento ou antes dele, o que é indicado
+    // junto à data para não parecer um erro.
+    let due_date = report.due_date.format(dates::DATE_FORMAT);
+    rows.push((
+        t("Data Provável do Parto (DPP)", lang).to_string(),
+        match report.due_date.cmp(&report.birthdate) {
+            std::cmp::Ordering::Greater => due_date.to_string(),
+            std::cmp::Ordering::Equal => tf("{} (no dia do nascimento)", lang, &[&due_date]),
+            std::cmp::Ordering::Less => tf("{} (antes do nascimento)", lang, &[&due_date]),
+        },
+    ));
+
+    // A convenção de idade altera apenas a linha da idade em anos.
+    rows.push(match convention {
+        AgeConvention::International => (
+            t("Idade Corrigida (Anos)", lang).to_string(),
+            tf(
+                "{} anos, {} meses e {} dias",
+                lang,
+                &[
+                    &corrected_age.years,
+                    &corrected_age.months,
+                    &corrected_age.days,
+                ],
+            ),
+        ),
+        _ => (
+            tf(
+                "Idade Corrigida (Anos, {})",
+                lang,
+                &[&t(convention.label(), lang).to_lowercase()],
+            ),
+            tf(
+                "{} anos",
+                lang,
+                &[&convention.years(
+                    corrected_age.years,
+                    report.corrected_birthdate,
+                    report.today,
+                )],
+            ),
+        ),
+    });
+    rows
+}
+
+/// Fórmulas usadas em cada linha de `result_rows`, com os valores intermediários
+/// do cálculo, para fins didáticos.
+fn result_formulas(report: &Report, convention: AgeConvention, lang: Lang) -> Vec<String> {
+    let total_days = report
+        .today
+        .signed_duration_since(report.birthdate)
+        .num_days();
+    let corrected_total_days = report.corrected.weeks * 7 + report.corrected.days_in_week;
+    let mut formulas = vec![
+        tf(
+            "idade cronológica = dias desde o nascimento ÷ 7 = {} ÷ 7; meses: {}",
+            lang,
+            &[&total_days, &t(report.month_count.label(), lang)],
+        ),
+        tf(
+            "idade corrigida = idade cronológica − prematuridade = {} − {} = {} dias",
+            lang,
+            &[&total_days, &report.prematurity_days, &corrected_total_days],
+        ),
+        tf(
+            "meses de calendário completos desde a data do termo ({}) e dias restantes",
+            lang,
+            &[&report.corrected_birthdate.format("%d/%m/%Y")],
+        ),
+    ];
+    if report.corrected_range.is_some() {
+        formulas.push(
+            t(
+                "idade corrigida calculada com o início e com o fim do intervalo de IG",
+                lang,
+            )
+            .to_string(),
+        );
+    }
+    formulas.push(tf(
+        "dias ÷ 7, com uma casa decimal = {} ÷ 7 e {} ÷ 7",
+        lang,
+        &[&total_days, &corrected_total_days],
+    ));
+    let total_gestational_days = report.post_menstrual_days - (report.day_of_life - 1);
+    formulas.push(tf(
+        "idade gestacional ao nascer + dias de vida = {} + {} dias",
+        lang,
+        &[&total_gestational_days, &(report.day_of_life - 1)],
+    ));
+    formulas.push(tf(
+        "DPP = nascimento + (280 − dias de gestação) = {} + ({} − {}) dias",
+        lang,
+        &[
+            &report.birthdate.format(dates::DATE_FORMAT),
+            &(FULL_TERM_WEEKS * 7),
+            &total_gestational_days,
+        ],
+    ));
+    formulas.push(match convention {
+        AgeConvention::International => tf(
+            "anos, meses e dias de calendário desde a data do termo ({})",
+            lang,
+            &[&report.corrected_birthdate.format("%d/%m/%Y")],
+        ),
+        _ => tf(
+            "ano atual − ano da data do termo + 1 = {} − {} + 1",
+            lang,
+            &[&report.today.year(), &report.corrected_birthdate.year()],
+        ),
+    });
+    formulas
+}
+
+/// Fórmulas usadas em cada linha de `detail_rows`.
+fn detail_formulas(report: &Report, lang: Lang) -> Vec<String> {
+    let total_gestational_days = report.post_menstrual_days - (report.day_of_life - 1);
+    let mut formulas = vec![
+        t(
+            "dias desde o nascimento + 1 (o nascimento é o 1º dia)",
+            lang,
+        )
+        .to_string(),
+        t("nascimento − (idade gestacional − 14 dias)", lang).to_string(),
+        tf(
+            "{} semanas − idade gestacional = {} − {} dias",
+            lang,
+            &[
+                &report.term_weeks,
+                &(report.term_weeks * 7),
+                &total_gestational_days,
+            ],
+        ),
+        t("nascimento + dias de prematuridade", lang).to_string(),
+        t(
+            "informado no formulário; usado nos percentis de crescimento",
+            lang,
+        )
+        .to_string(),
+    ];
+    if report.gestational_age_source.is_some() {
+        formulas.push(
+            t(
+                "a primeira fonte válida na ordem de prioridade das configurações",
+                lang,
+            )
+            .to_string(),
+        );
+    }
+    if report.observer.is_some() {
+        formulas.push(t("informado no formulário; não entra no cálculo", lang).to_string());
+    }
+    formulas
+}
+
+/// Junta linhas de rótulo e valor em texto, uma por linha. Se `formulas` for
+/// informado, cada linha é seguida pela fórmula correspondente.
+fn format_rows(rows: &[(String, String)], formulas: Option<&[String]>) -> String {
+    rows.iter()
+        .enumerate()
+        .map(
+            |(index, (label, value))| match formulas.and_then(|formulas| formulas.get(index)) {
+                Some(formula) => format!("{}: {}\n    ({})", label, value, formula),
+                None => format!("{}: {}", label, value),
+            },
+        )
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Formata o resultado do cálculo como texto simples, conforme o modo de exibição.
+fn format_result_text(report: &Report, settings: &Settings) -> String {
+    let lang = settings.language;
+    match settings.display_mode {
+        DisplayMode::Clinician => {
+            let formulas = result_formulas(report, settings.age_convention, lang);
+            format_rows(
+                &result_rows(report, settings.age_convention, lang),
+                settings.show_formulas.then_some(formulas.as_slice()),
+            )
+        }
+        DisplayMode::Parent => format_parent_text(report, settings.parent_week_fractions, lang),
+        DisplayMode::Complete => format_complete_text(report, lang),
+    }
+}
+
+/// Formata as três referências de idade lado a lado, cada uma com sua origem: a
+/// cronológica (desde o nascimento), a corrigida (desde o termo) e a pós-menstrual
+/// (desde a DUM, somando a idade gestacional ao nascer).
+fn format_complete_text(report: &Report, lang: Lang) -> String {
+    let chronological_age = &report.chronological;
+    let corrected_age = &report.corrected;
+    let days_since_birth = report.day_of_life - 1;
+    [
+        tf(
+            "Desde o nascimento (cronológica): {} semanas e {} dias | {} meses e {} dias",
+            lang,
+            &[
+                &chronological_age.total_weeks,
+                &(days_since_birth % 7),
+                &(chronological_age.years * 12 + chronological_age.months),
+                &chronological_age.days,
+            ],
+        ),
+        tf(
+            "Desde o termo (corrigida): {} semanas e {} dias | {}",
+            lang,
+            &[
+                &corrected_age.weeks,
+                &corrected_age.days_in_week,
+                &format_months_and_days(report, lang),
+            ],
+        ),
+        tf(
+            "Pós-menstrual (IG ao nascer + vida): {} semanas e {} dias",
+            lang,
+            &[
+                &(report.post_menstrual_days / 7),
+                &(report.post_menstrual_days % 7),
+            ],
+        ),
+    ]
+    .join("\n")
+}
+
+/// Data `days` dias antes de `today`, no formato do campo (DD/MM/AAAA).
+fn days_before_today(today: NaiveDate, days: i64) -> String {
+    (today - chrono::Duration::days(days))
+        .format(dates::DATE_FORMAT)
+        .to_string()
+}
+
+/// Formata uma idade em dias como semanas decimais, com uma casa ("19,4"). O
+/// separador decimal é a vírgula, exceto em inglês.
+fn format_decimal_weeks(days: i64, lang: Lang) -> String {
+    let weeks = format!("{:.1}", days as f64 / 7.0);
+    match lang {
+        Lang::En => weeks,
+        Lang::PtBr | Lang::Es => weeks.replace('.', ","),
+    }
+}
+
+/// Formata o resultado em frases simples, para explicar as idades às famílias.
+fn format_parent_text(report: &Report, week_fractions: bool, lang: Lang) -> String {
+    let chronological_age = &report.chronological;
+    let corrected_age = &report.corrected;
+    let mut text = tf(
+        "Seu bebê tem {} semanas de vida (cerca de {} meses).",
+        lang,
+        &[
+            &chronological_age.total_weeks,
+            &chronological_age.total_months,
+        ],
+    );
+    text.push('\n');
+    if let Some((earliest, latest)) = &report.corrected_range {
+        text.push_str(&tf(
+            "Como a data da gestação é incerta, o desenvolvimento dele deve ser comparado ao de um bebê de {} a {} semanas (idade corrigida).",
+            lang,
+            &[&earliest.weeks, &latest.weeks],
+        ));
+    } else if report.corrected_birthdate > report.birthdate {
+        text.push_str(&tf(
+            "Como nasceu antes do tempo, o desenvolvimento dele deve ser comparado ao de um bebê de {} (idade corrigida).",
+            lang,
+            &[&if week_fractions {
+                format_weeks_with_fraction(corrected_age.weeks, corrected_age.days_in_week, lang)
+            } else {
+                tf(
+                    "{} semanas e {} dias",
+                    lang,
+                    &[&corrected_age.weeks, &corrected_age.days_in_week],
+                )
+            }],
+        ));
+    } else {
+        text.push_str(t(
+            "Como nasceu a termo, não é preciso corrigir a idade.",
+            lang,
+        ));
+    }
+    if report.due_date > report.birthdate {
+        text.push('\n');
+        text.push_str(&tf(
+            "A data prevista para o parto era {}.",
+            lang,
+            &[&report.due_date.format(dates::DATE_FORMAT)],
+        ));
+    }
+    text
+}
+
+/// Formata semanas e dias com os dias como fração da semana quando o
+/// arredondamento é natural: 3 ou 4 dias viram "e meia", 2 dias "e um terço" e 5
+/// dias "e dois terços". Com 1 ou 6 dias, usa semanas e dias.
+fn format_weeks_with_fraction(weeks: i64, days: i64, lang: Lang) -> String {
+    let weeks_text = if weeks == 1 {
+        t("1 semana", lang).to_string()
+    } else {
+        tf("{} semanas", lang, &[&weeks])
+    };
+    match days {
+        0 => weeks_text,
+        2 => tf("{} e um terço", lang, &[&weeks_text]),
+        3 | 4 => tf("{} e meia", lang, &[&weeks_text]),
+        5 => tf("{} e dois terços", lang, &[&weeks_text]),
+        1 => tf("{} e 1 dia", lang, &[&weeks_text]),
+        _ => tf("{} e {} dias", lang, &[&weeks_text, &days]),
+    }
+}
+
+/// Cabeçalho das colunas do CSV de acompanhamento. O separador é ';', como esperam
+/// as planilhas configuradas em português.
+const CSV_HEADER: &str = "data_referencia;data_nascimento;ig_semanas;ig_dias;\
+     idade_cronologica_semanas;idade_corrigida_semanas;idade_corrigida_anos;\
+     idade_corrigida_meses;idade_corrigida_dias;bebe";
+
+/// Linha do CSV de acompanhamento com as idades do cálculo, terminada em '\n'. Em
+/// uma gestação múltipla, a última coluna traz o número do bebê (`baby`); com um
+/// bebê só, fica vazia.
+fn csv_row(report: &Report, baby: Option<usize>) -> String {
+    let gestational_age_days = report.post_menstrual_days - (report.day_of_life - 1);
+    format!(
+        "{};{};{};{};{};{};{};{};{};{}\n",
+        report.today.format(dates::DATE_FORMAT),
+        report.birthdate.format(dates::DATE_FORMAT),
+        gestational_age_days / 7,
+        gestational_age_days % 7,
+        report.chronological.total_weeks,
+        report.corrected.weeks,
+        report.corrected.years,
+        report.corrected.months,
+        report.corrected.days,
+        baby.map(|baby| baby.to_string()).unwrap_or_default()
+    )
+}
+
+/// Acrescenta uma linha ao CSV de acompanhamento. Um arquivo novo (ou vazio)
+/// recebe antes o cabeçalho; um arquivo existente nunca é sobrescrito.
+fn append_csv_row(path: &std::path::Path, row: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    if file.metadata()?.len() == 0 {
+        writeln!(file, "{}", CSV_HEADER)?;
+    }
+    file.write_all(row)
+}
+
+/// Linhas do PDF exportado: a data do cálculo, os dados informados e a
+/// classificação, seguidos do mesmo texto da exportação em texto.
+fn pdf_lines(report: &Report, settings: &Settings) -> Vec<pdf::Line> {
+    let lang = settings.language;
+    let gestational_age_days = report.post_menstrual_days - (report.day_of_life - 1);
+    let mut lines: Vec<pdf::Line> = [
+        tf(
+            "Calculado em {}",
+            lang,
+            &[&report.today.format(dates::DATE_FORMAT)],
+        ),
+        String::new(),
+        tf(
+            "Data de nascimento: {}",
+            lang,
+            &[&report.birthdate.format(dates::DATE_FORMAT)],
+        ),
+        tf(
+            "Idade gestacional ao nascer: {} semanas e {} dias",
+            lang,
+            &[&(gestational_age_days / 7), &(gestational_age_days % 7)],
+        ),
+    ]
+    .map(pdf::Line::from)
+    .into();
+    // A classificação e o aviso do fim da correção são os valores destacados: na
+    // cor da interface ou, no PDF em preto e branco, em negrito com um marcador.
+    let category = preterm_category(gestational_age_days);
+    lines.push(pdf::Line {
+        text: tf("Classificação: {}", lang, &[&t(category.label(), lang)]),
+        highlight: (category != PretermCategory::Term).then(|| {
+            let [r, g, b, _] = category.color(false).to_array();
+            [r, g, b]
+        }),
+    });
+    if let Some(weeks_left) = weeks_until_cutoff(report) {
+        lines.push(pdf::Line {
+            text: tf(
+                "Faltam {} semanas para a correção deixar de ser aplicada \
+                 ({} meses corrigidos).",
+                lang,
+                &[&weeks_left, &CORRECTION_CUTOFF_MONTHS],
+            ),
+            highlight: Some([230, 140, 0]),
+        });
+    }
+    lines.push(String::new().into());
+    lines.extend(
+        format_export_text(report, settings)
+            .lines()
+            .map(|line| line.to_string().into()),
+    );
+    lines
+}
+
+/// Formata o resultado de cada bebê com `format`. Em uma gestação múltipla, cada
+/// bloco é precedido de "Bebê 1", "Bebê 2"... e da data de nascimento.
+fn format_babies_text(
+    report: &Report,
+    sibling_reports: &[Report],
+    lang: Lang,
+    format: impl Fn(&Report) -> String,
+) -> String {
+    if sibling_reports.is_empty() {
+        return format(report);
+    }
+    numbered_babies(report, sibling_reports)
+        .map(|(number, report)| {
+            format!(
+                "=== {} ===\n{}",
+                baby_heading(number.unwrap_or(1), report, lang),
+                format(report)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Cada bebê com o seu número (1, 2, 3) em uma gestação múltipla, ou sem número
+/// quando há um bebê só.
+fn numbered_babies<'a>(
+    report: &'a Report,
+    sibling_reports: &'a [Report],
+) -> impl Iterator<Item = (Option<usize>, &'a Report)> {
+    let multiple = !sibling_reports.is_empty();
+    std::iter::once(report)
+        .chain(sibling_reports)
+        .enumerate()
+        .map(move |(index, report)| (multiple.then_some(index + 1), report))
+}
+
+/// Título do bloco de um bebê de uma gestação múltipla, como "Bebê 2 (nascimento
+/// em 02/01/2024)".
+fn baby_heading(number: usize, report: &Report, lang: Lang) -> String {
+    tf(
+        "Bebê {} (nascimento em {})",
+        lang,
+        &[&number, &report.birthdate.format(dates::DATE_FORMAT)],
+    )
+}
+
+/// Conteúdo do arquivo exportado em `format`, com um bloco, uma seção ou uma linha
+/// por bebê em uma gestação múltipla.
+fn export_contents(
+    format: ExportFormat,
+    report: &Report,
+    sibling_reports: &[Report],
+    settings: &Settings,
+) -> Vec<u8> {
+    let lang = settings.language;
+    match format {
+        ExportFormat::Text => format_babies_text(report, sibling_reports, lang, |report| {
+            format_export_text(report, settings)
+        })
+        .into_bytes(),
+        ExportFormat::Fhir => {
+            let observations: Vec<fhir::Observation> = numbered_babies(report, sibling_reports)
+                .flat_map(|(number, report)| {
+                    let subject = number.map(|number| tf("Bebê {}", lang, &[&number]));
+                    fhir::observations(&report_metrics(report), report.today, subject)
+                })
+                .collect();
+            serde_json::to_string_pretty(&observations)
+                .unwrap_or_default()
+                .into_bytes()
+        }
+        ExportFormat::Pdf => {
+            let mut lines = Vec::new();
+            for (number, report) in numbered_babies(report, sibling_reports) {
+                if let Some(number) = number {
+                    if !lines.is_empty() {
+                        lines.push(String::new().into());
+                    }
+                    lines.push(pdf::Line {
+                        text: baby_heading(number, report, lang),
+                        highlight: None,
+                    });
+                }
+                lines.extend(pdf_lines(report, settings));
+            }
+            pdf::document(
+                t("Calculadora de Idade Gestacional do Bebê", lang),
+                &lines,
+                settings.monochrome_export,
+            )
+        }
+        ExportFormat::Csv => numbered_babies(report, sibling_reports)
+            .map(|(number, report)| csv_row(report, number))
+            .collect::<String>()
+            .into_bytes(),
+    }
+}
+
+/// Percentil aproximado do peso informado pelas curvas de Fenton, na idade
+/// pós-menstrual do cálculo. Sem peso, retorna `None`; sem o sexo ou fora das
+/// curvas, explica por que o percentil não foi calculado.
+fn weight_percentile_text(report: &Report, lang: Lang) -> Option<String> {
+    let grams = report.weight_grams?;
+    let Some(sex) = report.sex else {
+        return Some(
+            t(
+                "Sexo não informado: o percentil do peso não pode ser calculado.",
+                lang,
+            )
+            .to_string(),
+        );
+    };
+    Some(
+        match growth::fenton_percentile(report.post_menstrual_days, sex, grams) {
+            Ok(percentile) => tf(
+                "Peso no percentil ~{} (Fenton)",
+                lang,
+                &[&(percentile.round() as u32).clamp(1, 99)],
+            ),
+            Err(growth::NoPercentile::NotComputable) => {
+                t("Percentil do peso: não calculável.", lang).to_string()
+            }
+            Err(growth::NoPercentile::OutOfRange) => tf(
+                "Percentil do peso indisponível: as curvas de Fenton vão de {} a {} semanas \
+                 de idade pós-menstrual.",
+                lang,
+                &[&growth::FIRST_WEEK, &growth::LAST_WEEK],
+            ),
+        },
+    )
+}
+
+/// Formata o resultado completo para exportação em texto: as idades seguidas das
+/// consultas de seguimento.
+fn format_export_text(report: &Report, settings: &Settings) -> String {
+    let lang = settings.language;
+    let formulas = detail_formulas(report, lang);
+    let details = format_rows(
+        &detail_rows(report, lang),
+        settings.show_formulas.then_some(formulas.as_slice()),
+    );
+    format!(
+        "{}\n\n{}:\n{}\n\n{}",
+        format_result_text(report, settings),
+        t("Detalhes do cálculo", lang),
+        details,
+        format_checkpoints_text(&scheduled_checkpoints(report, settings), lang)
+    )
+}
+
+/// Linhas do painel "E se tivesse nascido a termo?": a idade cronológica (que
+/// seria a corrigida de um bebê a termo), a idade corrigida real e a diferença.
+fn what_if_rows(report: &Report, lang: Lang) -> Vec<(String, String)> {
+    let total_days = report.day_of_life - 1;
+    let corrected_days = report.corrected.weeks * 7 + report.corrected.days_in_week;
+    let gap = total_days - corrected_days;
+    vec![
+        (
+            t("Idade corrigida se nascesse a termo", lang).to_string(),
+            tf(
+                "{} semanas e {} dias",
+                lang,
+                &[&(total_days / 7), &(total_days % 7)],
+            ),
+        ),
+        (
+            t("Idade corrigida real", lang).to_string(),
+            tf(
+                "{} semanas e {} dias",
+                lang,
+                &[&(corrected_days / 7), &(corrected_days % 7)],
+            ),
+        ),
+        (
+            t("Diferença", lang).to_string(),
+            tf(
+                "{} dias ({} semanas e {} dias)",
+                lang,
+                &[&gap, &(gap / 7), &(gap % 7)],
+            ),
+        ),
+    ]
+}
+
+/// Linhas com as métricas secundárias do cálculo (rótulo e valor).
+fn detail_rows(report: &Report, lang: Lang) -> Vec<(String, String)> {
+    let mut rows = vec![
+        (
+            t("Dia de Vida", lang).to_string(),
+            tf("{}º dia", lang, &[&report.day_of_life]),
+        ),
+        (
+            t("Data Estimada da Concepção", lang).to_string(),
+            report.conception_date.format("%d/%m/%Y").to_string(),
+        ),
+        (
+            t("Dias de Prematuridade", lang).to_string(),
+            tf("{} dias", lang, &[&report.prematurity_days]),
+        ),
+        (
+            tf("Data do Termo ({} semanas)", lang, &[&report.term_weeks]),
+            report.corrected_birthdate.format("%d/%m/%Y").to_string(),
+        ),
+    ];
+    rows.push((
+        t("Sexo", lang).to_string(),
+        t(growth::Sex::label(report.sex), lang).to_string(),
+    ));
+    if let Some(source) = report.gestational_age_source {
+        rows.push((
+            t("Fonte da IG", lang).to_string(),
+            t(source.label(), lang).to_string(),
+        ));
+    }
+    if let Some(observer) = &report.observer {
+        rows.push((t("Observador/Fonte", lang).to_string(), observer.clone()));
+    }
+    rows
+}
+
+/// Formata a idade corrigida no formato usado nas evoluções pediátricas, "X meses e
+/// Y dias corrigidos", contando meses de calendário a partir da data do termo (e não
+/// a partir do total de semanas). Antes da data do termo, a idade corrigida é zero.
+fn format_corrected_months_and_days(report: &Report, lang: Lang) -> String {
+    tf(
+        "{} corrigidos",
+        lang,
+        &[&format_months_and_days(report, lang)],
+    )
+}
+
+/// Meses de calendário e dias desde a data do termo, como "2 meses e 19 dias".
+fn format_months_and_days(report: &Report, lang: Lang) -> String {
+    let (months, days) = if report.today < report.corrected_birthdate {
+        (0, 0)
+    } else {
+        let age = calculate_chronological_age(report.corrected_birthdate, report.today);
+        (age.years * 12 + age.months, age.days)
+    };
+    tf(
+        "{} {} e {} {}",
+        lang,
+        &[
+            &months,
+            &t(if months == 1 { "mês" } else { "meses" }, lang),
+            &days,
+            &t(if days == 1 { "dia" } else { "dias" }, lang),
+        ],
+    )
+}
+
+/// Formata um resumo numérico que cabe em uma mensagem SMS (`SMS_MAX_CHARS`).
+/// Os rótulos são abreviados e sem acentos (que reduzem o limite de um SMS). Os
+/// campos opcionais só entram se couberem inteiros.
+fn format_sms_summary(report: &Report, lang: Lang) -> String {
+    let chronological_age = &report.chronological;
+    let corrected_age = &report.corrected;
+    let mut summary = tf(
+        "Id.cron {}sem ({}m); Id.corr {}s{}d ({}a{}m{}d)",
+        lang,
+        &[
+            &chronological_age.total_weeks,
+            &chronological_age.total_months,
+            &corrected_age.weeks,
+            &corrected_age.days_in_week,
+            &corrected_age.years,
+            &corrected_age.months,
+            &corrected_age.days,
+        ],
+    );
+
+    let mut optional = vec![tf(
+        "IPM {}s{}d",
+        lang,
+        &[
+            &(report.post_menstrual_days / 7),
+            &(report.post_menstrual_days % 7),
+        ],
+    )];
+    if let Some(next) = report
+        .checkpoints
+        .iter()
+        .find(|checkpoint| checkpoint.date >= report.today)
+    {
+        optional.push(tf(
+            "Consulta {}m corr: {}",
+            lang,
+            &[&next.corrected_months, &next.date.format("%d/%m/%y")],
+        ));
+    }
+    for field in optional {
+        if summary.chars().count() + 2 + field.chars().count() <= SMS_MAX_CHARS {
+            summary.push_str("; ");
+            summary.push_str(&field);
+        }
+    }
+
+    // Garantia final: nunca ultrapassa o limite, mesmo com valores extremos.
+    summary.chars().take(SMS_MAX_CHARS).collect()
+}
+
+/// Formata o resultado do cálculo como um fragmento HTML (tabelas de idades e de
+/// consultas de seguimento), pronto para ser colado em editores de texto formatado.
+fn format_result_html(report: &Report, settings: &Settings) -> String {
+    let lang = settings.language;
+    let mut html = String::from("<table>\n");
+    for (label, value) in result_rows(report, settings.age_convention, lang) {
+        html.push_str(&format!("<tr><th>{}</th><td>{}</td></tr>\n", label, value));
+    }
+    html.push_str("</table>\n");
+
+    // Tabela com os detalhes do cálculo.
+    html.push_str("<table>\n");
+    for (label, value) in detail_rows(report, lang) {
+        // O observador é texto livre, então os valores são escapados.
+        let value = value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;");
+        html.push_str(&format!("<tr><th>{}</th><td>{}</td></tr>\n", label, value));
+    }
+    html.push_str("</table>\n");
+
+    // Tabela com as consultas de seguimento.
+    html.push_str(&format!(
+        "<table>\n<tr><th>{}</th><th>{}</th></tr>\n",
+        t("Idade Corrigida", lang),
+        t("Data", lang)
+    ));
+    for checkpoint in scheduled_checkpoints(report, settings) {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td></tr>\n",
+            tf("{} meses", lang, &[&checkpoint.corrected_months]),
+            checkpoint.date.format("%d/%m/%Y")
+        ));
+    }
+    html.push_str("</table>");
+    html
+}
+
+/// Formata o resultado de cada bebê como fragmento HTML; em uma gestação múltipla,
+/// as tabelas de cada bebê vêm precedidas de um título com o número dele.
+fn format_babies_html(report: &Report, sibling_reports: &[Report], settings: &Settings) -> String {
+    numbered_babies(report, sibling_reports)
+        .map(|(number, report)| match number {
+            Some(number) => format!(
+                "<h3>{}</h3>\n{}",
+                baby_heading(number, report, settings.language),
+                format_result_html(report, settings)
+            ),
+            None => format_result_html(report, settings),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Valida e converte os campos do formulário (ou da linha de comando), retornando
+/// a mensagem de erro a ser exibida caso algum seja inválido.
+fn parse_fields(fields: &FormFields, settings: &Settings) -> Result<Inputs, String> {
+    let lang = settings.language;
+    // Remove espaços em branco no início e no fim das entradas (comum ao colar).
+    let birth_date_str = fields.birth_date.trim();
+    let gestational_weeks_str = fields.gestational_weeks.trim();
+    let gestational_days_str = fields.gestational_days.trim();
+
+    // A data de referência é opcional; quando informada, substitui a data atual
+    // em todos os cálculos.
+    let now = Utc::now().date_naive();
+    let reference_date_str = fields.reference_date.trim();
+    let reference_date = if reference_date_str.is_empty() {
+        None
+    } else {
+        Some(
+            dates::parse_date_localized(reference_date_str, now, dates::system_date_order())
+                .ok_or_else(|| {
+                    t(
+                        "Formato da data de referência inválido. Use DD/MM/AAAA.",
+                        lang,
+                    )
+                    .to_string()
+                })?,
+        )
+    };
+    let today = reference_date.unwrap_or(now);
+
+    // Valida e converte a data de nascimento.
+    let birthdate = dates::parse_date_localized(birth_date_str, today, dates::system_date_order())
+        .ok_or_else(|| t("Formato de data inválido. Use DD/MM/AAAA.", lang).to_string())?;
+    validate_birthdate(birthdate, today, lang)?;
+    log::debug!(
+        "Nascimento \"{}\" lido como {}; cálculo em {} ({})",
+        birth_date_str,
+        birthdate,
+        today,
+        if reference_date.is_some() {
+            "data de referência"
+        } else {
+            "data atual"
+        }
+    );
+
+    // Gêmeos e trigêmeos costumam nascer no mesmo dia: vazia, a data do bebê é a
+    // do Bebê 1.
+    let mut sibling_birthdates = Vec::new();
+    for (index, date_str) in fields.sibling_birth_dates.iter().enumerate() {
+        let date_str = date_str.trim();
+        let baby_error = |message: String| tf("Bebê {}: {}", lang, &[&(index + 2), &message]);
+        let sibling_birthdate = if date_str.is_empty() {
+            birthdate
+        } else {
+            dates::parse_date_localized(date_str, today, dates::system_date_order()).ok_or_else(
+                || baby_error(t("Formato de data inválido. Use DD/MM/AAAA.", lang).to_string()),
+            )?
+        };
+        validate_birthdate(sibling_birthdate, today, lang).map_err(baby_error)?;
+        sibling_birthdates.push(sibling_birthdate);
+    }
+
+    // A DUM é opcional; quando informada, precisa ser uma data válida.
+    let lmp_str = fields.lmp.trim();
+    let last_menstrual_period = if lmp_str.is_empty() {
+        None
+    } else {
+        Some(
+            dates::parse_date_localized(lmp_str, today, dates::system_date_order())
+                .ok_or_else(|| t("Formato da DUM inválido. Use DD/MM/AAAA.", lang).to_string())?,
+        )
+    };
+
+    // Com a DUM, a idade gestacional pode vir de duas fontes: usa a primeira válida
+    // na ordem de prioridade das configurações. Sem nenhuma válida, o erro é o do
+    // campo de semanas, e uma DUM após o nascimento é apontada depois, no cálculo.
+    let informed =
+        parse_informed_gestational_age(gestational_weeks_str, gestational_days_str, settings);
+    let lmp_gestational_days = last_menstrual_period
+        .map(|lmp| birthdate.signed_duration_since(lmp).num_days())
+        .filter(|&days| days >= 0 && validate_gestational_weeks((days / 7) as i32, lang).is_ok());
+    let gestational_age_source = select_gestational_age_source(
+        settings.gestational_age_priority,
+        informed.is_ok(),
+        lmp_gestational_days.is_some(),
+    );
+    let (gestational_weeks, gestational_weeks_max, gestational_days, gestational_age_source) =
+        match (gestational_age_source, lmp_gestational_days) {
+            (Some(GestationalAgeSource::Lmp), Some(days)) => (
+                (days / 7) as i32,
+                None,
+                (days % 7) as i32,
+                GestationalAgeSource::Lmp,
+            ),
+            _ => {
+                let (weeks, weeks_max, days) = informed?;
+                (weeks, weeks_max, days, GestationalAgeSource::Informed)
+            }
+        };
+    log::debug!(
+        "Idade gestacional de {} semanas e {} dias, pela fonte \"{}\"",
+        gestational_weeks,
+        gestational_days,
+        gestational_age_source.label()
+    );
+
+    // O observador é um texto livre e opcional, que não afeta o cálculo.
+    let observer = Some(fields.observer.trim())
+        .filter(|observer| !observer.is_empty())
+        .map(str::to_string);
+
+    // O peso é opcional e só serve para o percentil; aceita vírgula ou ponto.
+    let weight_str = fields.weight.trim();
+    let weight_grams = if weight_str.is_empty() {
+        None
+    } else {
+        Some(
+            weight_str
+                .replace(',', ".")
+                .parse::<f64>()
+                .ok()
+                .filter(|grams| (MIN_WEIGHT_GRAMS..=MAX_WEIGHT_GRAMS).contains(grams))
+                .ok_or_else(|| {
+                    tf(
+                        "Peso inválido. Informe o peso em gramas, entre {} e {}.",
+                        lang,
+                        &[&MIN_WEIGHT_GRAMS, &MAX_WEIGHT_GRAMS],
+                    )
+                })?,
+        )
+    };
+
+    Ok(Inputs {
+        birthdate,
+        gestational_weeks,
+        gestational_weeks_max,
+        gestational_days,
+        gestational_age_source,
+        last_menstrual_period,
+        observer,
+        reference_date,
+        sibling_birthdates,
+        weight_grams,
+        sex: fields.sex,
+    })
+}
+
+/// Interpreta a idade gestacional digitada nos campos de semanas e dias, retornando
+/// as semanas (e o fim do intervalo, se houver) e os dias.
+fn parse_informed_gestational_age(
+    weeks_str: &str,
+    days_str: &str,
+    settings: &Settings,
+) -> Result<(i32, Option<i32>, i32), String> {
+    let lang = settings.language;
+    let weeks_input = parse_gestational_weeks(weeks_str, settings.decimal_gestational_weeks, lang)?;
+    log::debug!(
+        "Idade gestacional \"{}\" lida como {:?}",
+        weeks_str,
+        weeks_input
+    );
+    let (gestational_weeks, gestational_weeks_max) = match weeks_input {
+        WeeksInput::Single(weeks) => (weeks, None),
+        WeeksInput::Range(start, end) => (start, Some(end)),
+        WeeksInput::Decimal { weeks, .. } | WeeksInput::Combined { weeks, .. } => (weeks, None),
+    };
+
+    // Rejeita idades gestacionais fora da faixa suportada, inclusive no fim de
+    // um intervalo.
+    validate_gestational_weeks(gestational_weeks, lang)?;
+    if let Some(weeks_max) = gestational_weeks_max {
+        validate_gestational_weeks(weeks_max, lang)?;
+    }
+
+    // Valida e converte os dias gestacionais. Com semanas decimais ou combinadas
+    // ("32+4"), os dias já vêm do campo de semanas e o campo de dias deve ficar vazio.
+    let gestational_days = match weeks_input {
+        WeeksInput::Decimal { days, .. } | WeeksInput::Combined { days, .. } => {
+            if !matches!(days_str, "" | "0") {
+                return Err(t(
+                    "Os dias já foram informados no campo de semanas; deixe o campo de dias vazio.",
+                    lang,
+                )
+                .to_string());
+            }
+            days
+        }
+        _ => parse_gestational_days(days_str, lang)?,
+    };
+    Ok((gestational_weeks, gestational_weeks_max, gestational_days))
+}
+
+/// Escolhe a fonte da idade gestacional: a primeira da ordem de `priority` que
+/// tem um valor válido, ou `None` se nenhuma tiver.
+fn select_gestational_age_source(
+    priority: GestationalAgePriority,
+    informed_valid: bool,
+    lmp_valid: bool,
+) -> Option<GestationalAgeSource> {
+    priority.order().into_iter().find(|source| match source {
+        GestationalAgeSource::Informed => informed_valid,
+        GestationalAgeSource::Lmp => lmp_valid,
+    })
+}
+
+/// Calcula as idades e datas de todos os bebês a partir das entradas já validadas,
+/// com os avisos e a nota a exibir. Retorna a mensagem de erro se os dados forem
+/// incoerentes (como a DUM após o nascimento).
+fn calculate_inputs(inputs: Inputs, settings: &Settings) -> Result<Calculation, String> {
+    let lang = settings.language;
+    let Inputs {
+        birthdate,
+        gestational_weeks,
+        gestational_weeks_max,
+        gestational_days,
+        gestational_age_source,
+        last_menstrual_period,
+        observer,
+        reference_date,
+        sibling_birthdates,
+        weight_grams,
+        sex,
+    } = inputs;
+    let mut warnings = Vec::new();
+
+    // Prematuros extremos (22–23 semanas) são calculados normalmente, mas com aviso.
+    if gestational_weeks < PERIVIABLE_WEEKS {
+        warnings.push(tf(
+            "Atenção: prematuro extremo ({} semanas), no limite da viabilidade. \
+             Interprete a idade corrigida com cautela.",
+            lang,
+            &[&gestational_weeks],
+        ));
+    }
+
+    // Confere a idade gestacional informada com a DUM, se houver.
+    if let Some(lmp) = last_menstrual_period {
+        let total_gestational_days = (gestational_weeks * 7 + gestational_days) as i64;
+        let check = check_lmp(lmp, birthdate, total_gestational_days);
+        log::debug!("DUM {}: {:?}", lmp, check);
+        match check {
+            LmpCheck::Consistent => {}
+            LmpCheck::AfterBirth => {
+                return Err(
+                    t("A DUM não pode ser posterior à data de nascimento.", lang).to_string(),
+                );
+            }
+            LmpCheck::Discrepant {
+                lmp_gestational_days,
+                difference_days,
+            } => warnings.push(tf(
+                "A DUM indica {} semanas e {} dias ao nascer, {} dias de diferença da idade gestacional informada. Confira os dados.",
+                lang,
+                &[
+                    &(lmp_gestational_days / 7),
+                    &(lmp_gestational_days % 7),
+                    &difference_days.abs(),
+                ],
+            )),
+        }
+    }
+
+    // Obtém a data de referência (ou a atual) e calcula todas as idades e datas.
+    // Com um intervalo de idade gestacional, o resultado principal usa o início
+    // do intervalo e a idade corrigida é calculada também nos dois extremos.
+    let today = reference_date.unwrap_or_else(|| Utc::now().date_naive());
+    let build_report = |birthdate| {
+        let mut report = compute_all(
+            birthdate,
+            gestational_weeks,
+            gestational_days,
+            today,
+            settings.month_count,
+            settings.correction_term.weeks(),
+        );
+        report.corrected_range = gestational_weeks_max.map(|weeks_max| {
+            (
+                calculate_corrected_age(
+                    birthdate,
+                    today,
+                    gestational_weeks,
+                    gestational_days,
+                    settings.correction_term.weeks(),
+                ),
+                calculate_corrected_age(
+                    birthdate,
+                    today,
+                    weeks_max,
+                    gestational_days,
+                    settings.correction_term.weeks(),
+                ),
+            )
+        });
+        report.observer = observer.clone();
+        report.gestational_age_source = last_menstrual_period.map(|_| gestational_age_source);
+        report
+    };
+    let mut report = build_report(birthdate);
+    report.weight_grams = weight_grams;
+    report.sex = sex;
+    log::debug!(
+        "{} dias de prematuridade, termo em {}, {} dias de vida, idade corrigida de {} \
+         semanas e {} dias",
+        report.prematurity_days,
+        report.corrected_birthdate,
+        report.day_of_life,
+        report.corrected.weeks,
+        report.corrected.days_in_week
+    );
+    // Os outros bebês de uma gestação múltipla compartilham a idade gestacional.
+    let sibling_reports = sibling_birthdates.into_iter().map(build_report).collect();
+    // Sem prematuridade, a idade corrigida é a própria cronológica; a nota deixa
+    // claro que nenhuma correção foi aplicada.
+    let info = (report.prematurity_days == 0)
+        .then(|| t("Bebê a termo: idade corrigida igual à cronológica.", lang).to_string());
+    Ok(Calculation {
+        report,
+        sibling_reports,
+        warnings,
+        info,
+    })
+}
+
+/// Indicação do termo de referência exibida junto aos campos de idade gestacional:
+/// sempre que o termo não for o padrão de 40 semanas e, no padrão, apenas se a
+/// configuração pedir.
+fn term_indicator(settings: &Settings) -> Option<String> {
+    let weeks = settings.correction_term.weeks();
+    (weeks != FULL_TERM_WEEKS || settings.show_default_term).then(|| {
+        tf(
+            "Termo de referência: {} semanas",
+            settings.language,
+            &[&weeks],
+        )
+    })
+}
+
+/// Consultas de seguimento com as datas em que devem ser marcadas, conforme os
+/// dias de atendimento configurados.
+fn scheduled_checkpoints(report: &Report, settings: &Settings) -> Vec<Checkpoint> {
+    report
+        .checkpoints
+        .iter()
+        .map(|checkpoint| Checkpoint {
+            corrected_months: checkpoint.corrected_months,
+            date: settings.appointment_date(checkpoint.date),
+        })
+        .collect()
+}
+
+/// Métricas exportadas no formato FHIR simplificado, todas em dias (unidade UCUM
+/// "d"). Os códigos são os documentados para os sistemas que importam o arquivo.
+fn report_metrics(report: &Report) -> Vec<fhir::Metric> {
+    let gestational_age_days = report.post_menstrual_days - (report.day_of_life - 1);
+    let metric = |code, value| fhir::Metric {
+        code,
+        value,
+        unit: "d",
+    };
+    vec![
+        metric("idade-gestacional-ao-nascer", gestational_age_days),
+        metric("idade-cronologica", report.day_of_life - 1),
+        metric(
+            "idade-corrigida",
+            report.corrected.weeks * 7 + report.corrected.days_in_week,
+        ),
+        metric("idade-pos-menstrual", report.post_menstrual_days),
+        metric("dias-de-prematuridade", report.prematurity_days),
+        metric("dia-de-vida", report.day_of_life),
+    ]
+}
+
+/// Lista rotulada com todas as datas calculadas para o bebê, para montar o plano
+/// de seguimento. As consultas seguem os dias de atendimento configurados.
+fn format_all_dates(report: &Report, settings: &Settings) -> String {
+    let lang = settings.language;
+    let mut entries = vec![
+        (t("Data de nascimento", lang).to_string(), report.birthdate),
+        (
+            t("Data estimada da concepção", lang).to_string(),
+            report.conception_date,
+        ),
+        (
+            tf(
+                "Data do termo ({} semanas / idade corrigida zero)",
+                lang,
+                &[&report.term_weeks],
+            ),
+            report.corrected_birthdate,
+        ),
+    ];
+    if let Some(birthday) = &report.next_month_birthday {
+        entries.push((
+            tf(
+                "Próximo mês completo ({} meses corrigidos)",
+                lang,
+                &[&birthday.corrected_months],
+            ),
+            birthday.date,
+        ));
+    }
+    for checkpoint in scheduled_checkpoints(report, settings) {
+        entries.push((
+            tf(
+                "Consulta de {} meses corrigidos",
+                lang,
+                &[&checkpoint.corrected_months],
+            ),
+            checkpoint.date,
+        ));
+    }
+    entries
+        .iter()
+        .map(|(label, date)| format!("{}: {}", label, date.format(dates::DATE_FORMAT)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Formata as consultas de seguimento como texto simples, uma por linha.
+fn format_checkpoints_text(checkpoints: &[Checkpoint], lang: Lang) -> String {
+    let mut text = format!("{}:", t("Consultas de seguimento (idade corrigida)", lang));
+    for checkpoint in checkpoints {
+        text.push_str(&format!(
+            "\n{}: {}",
+            tf("{} meses", lang, &[&checkpoint.corrected_months]),
+            checkpoint.date.format("%d/%m/%Y")
+        ));
+    }
+    text
+}
+
+/// Título da janela minimizada: a idade corrigida em `today` do bebê do último
+/// cálculo, mesmo que ele tenha sido feito em outra data de referência.
+fn glance_title(report: &Report, name: Option<&str>, today: NaiveDate, lang: Lang) -> String {
+    let gestational_age_days = report.post_menstrual_days - (report.day_of_life - 1);
+    let current = compute_all(
+        report.birthdate,
+        (gestational_age_days / 7) as i32,
+        (gestational_age_days % 7) as i32,
+        today,
+        report.month_count,
+        report.term_weeks,
+    );
+    let age = tf(
+        "Idade corrigida hoje: {} semanas e {} dias",
+        lang,
+        &[&current.corrected.weeks, &current.corrected.days_in_week],
+    );
+    match name {
+        Some(name) => format!("{} – {}", name, age),
+        None => age,
+    }
+}
+
+/// Calcula todas as idades e datas de um bebê na data `today`, com os meses totais
+/// contados conforme `month_count` e a idade corrigida para um termo de `term_weeks`
+/// semanas.
+fn compute_all(
+    birthdate: NaiveDate,
     gestational_weeks: i32,
     gestational_days: i32,
-) -> CorrectedAge {
-    // Calcula o total de dias de gestação.
-    let total_gestational_days = gestational_weeks * 7 + gestational_days;
-    // Um termo completo é considerado 40 semanas.
-    let full_term_days = 40 * 7;
-    let prematurity_days = full_term_days - total_gestational_days;
-
-    // Se não for prematuro, a idade corrigida é a mesma que a cronológica.
-    if prematurity_days <= 0 {
-        let chronological = calculate_chronological_age(birthdate, today);
-        let total_days = today.signed_duration_since(birthdate).num_days();
-        return CorrectedAge {
-            years: chronological.years,
-            months: chronological.months,
-            days: chronological.days,
-            weeks: chronological.total_weeks,
-            days_in_week: total_days % 7,
-            total_months: chronological.total_months,
+    today: NaiveDate,
+    month_count: MonthCount,
+    term_weeks: i32,
+) -> Report {
+    let corrected_birthdate =
+        calculate_corrected_birthdate(birthdate, gestational_weeks, gestational_days, term_weeks);
+    let total_gestational_days = (gestational_weeks * 7 + gestational_days) as i64;
+    let total_days = today.signed_duration_since(birthdate).num_days();
+    let mut chronological = calculate_chronological_age(birthdate, today);
+    chronological.total_months =
+        month_count.total_months(chronological.years, chronological.months, total_days);
+    let mut corrected = calculate_corrected_age(
+        birthdate,
+        today,
+        gestational_weeks,
+        gestational_days,
+        term_weeks,
+    );
+    corrected.total_months = month_count.total_months(
+        corrected.years,
+        corrected.months,
+        corrected.weeks * 7 + corrected.days_in_week,
+    );
+    Report {
+        today,
+        birthdate,
+        corrected_birthdate,
+        due_date: calculate_due_date(birthdate, gestational_weeks, gestational_days),
+        chronological,
+        corrected,
+        prematurity_days: calculate_prematurity_days(
+            gestational_weeks,
+            gestational_days,
+            term_weeks,
+        ),
+        post_menstrual_days: calculate_post_menstrual_days(
+            birthdate,
+            today,
+            gestational_weeks,
+            gestational_days,
+        ),
+        day_of_life: total_days + 1,
+        conception_date: birthdate - chrono::Duration::days(total_gestational_days - 14),
+        checkpoints: calculate_checkpoints(corrected_birthdate),
+        next_month_birthday: next_corrected_month_birthday(corrected_birthdate, today),
+        corrected_range: None,
+        observer: None,
+        gestational_age_source: None,
+        month_count,
+        term_weeks,
+        weight_grams: None,
+        sex: None,
+    }
+}
+
+/// Interpreta o campo de semanas gestacionais: um número inteiro, semanas e dias
+/// juntos ("IG 32+4 sem", veja `extract_gestational_age`), um intervalo ("31-33")
+/// ou, se `decimal_allowed`, um número decimal com vírgula ou ponto ("32,5" são 32
+/// semanas e 4 dias, arredondando a fração para o dia mais próximo).
+fn parse_gestational_weeks(
+    input: &str,
+    decimal_allowed: bool,
+    lang: Lang,
+) -> Result<WeeksInput, String> {
+    let input = input.trim();
+    if let Some((weeks, days)) = extract_gestational_age(input) {
+        return Ok(WeeksInput::Combined { weeks, days });
+    }
+    // Um número negativo ("-5") não é um intervalo: chega à validação da faixa.
+    if let Ok(weeks) = i32::from_str(input) {
+        return Ok(WeeksInput::Single(weeks));
+    }
+    if let Some((start, end)) = input.split_once(['-', '–']) {
+        let (Ok(start), Ok(end)) = (i32::from_str(start.trim()), i32::from_str(end.trim())) else {
+            return Err(t(
+                "Intervalo de idade gestacional inválido. Use, por exemplo, 31-33.",
+                lang,
+            )
+            .to_string());
+        };
+        if end <= start {
+            return Err(t(
+                "O fim do intervalo de idade gestacional deve ser maior que o início.",
+                lang,
+            )
+            .to_string());
+        }
+        return Ok(WeeksInput::Range(start, end));
+    }
+
+    // "32.5" falharia no 'i32::from_str' com uma mensagem genérica; reconhece o
+    // decimal para explicar como informá-lo.
+    let decimal = input
+        .replace(',', ".")
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite() && *value >= 0.0);
+    match decimal {
+        Some(value) if decimal_allowed => {
+            let total_days = (value * 7.0).round() as i32;
+            Ok(WeeksInput::Decimal {
+                weeks: total_days / 7,
+                days: total_days % 7,
+            })
+        }
+        Some(_) => Err(t(
+            "Idade gestacional com casas decimais não é aceita. Informe semanas e dias em \
+             campos separados, ou ative as semanas decimais em Opções > Configurações.",
+            lang,
+        )
+        .to_string()),
+        None => Err(t("Idade gestacional deve ser um número.", lang).to_string()),
+    }
+}
+
+/// Confere se a idade gestacional, em semanas, está entre `MIN_GESTATIONAL_WEEKS`
+/// e `MAX_GESTATIONAL_WEEKS`.
+fn validate_gestational_weeks(weeks: i32, lang: Lang) -> Result<(), String> {
+    if weeks < MIN_GESTATIONAL_WEEKS {
+        return Err(tf(
+            "Idade gestacional mínima suportada é de {} semanas.",
+            lang,
+            &[&MIN_GESTATIONAL_WEEKS],
+        ));
+    }
+    if weeks > MAX_GESTATIONAL_WEEKS {
+        return Err(tf(
+            "Idade gestacional máxima suportada é de {} semanas. Confira o valor digitado.",
+            lang,
+            &[&MAX_GESTATIONAL_WEEKS],
+        ));
+    }
+    Ok(())
+}
+
+/// Interpreta o campo de dias na semana de nascimento, que vai de 0 a 6: com 7
+/// dias, a semana já estaria completa. O campo vazio vale 0, já que é comum
+/// informar apenas as semanas; texto que não é número continua sendo rejeitado.
+fn parse_gestational_days(input: &str, lang: Lang) -> Result<i32, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(0);
+    }
+    let days = i32::from_str(input)
+        .map_err(|_| t("Dias na semana de nascimento devem ser um número.", lang).to_string())?;
+    if !(0..=6).contains(&days) {
+        return Err(t("Dias devem estar entre 0 e 6.", lang).to_string());
+    }
+    Ok(days)
+}
+
+/// Separa uma idade gestacional combinada em semanas e dias. Aceita as formas de
+/// `extract_gestational_age` e também dois números separados por espaço ("32 4").
+fn split_gestational_age(input: &str) -> Option<(i32, i32)> {
+    extract_gestational_age(input).or_else(|| {
+        let (weeks, days) = input.trim().split_once(' ')?;
+        Some((
+            i32::from_str(weeks.trim()).ok()?,
+            i32::from_str(days.trim()).ok()?,
+        ))
+    })
+}
+
+/// Procura semanas e dias em um texto colado de um laudo, como "IG 32+4 sem",
+/// "32s4d", "32 semanas e 4 dias" ou "32w 4d": o primeiro número seguido de '+',
+/// 's' (ou "sem", "semanas") ou 'w' e de outro número, de 0 a 6.
+fn extract_gestational_age(input: &str) -> Option<(i32, i32)> {
+    let chars: Vec<char> = input.to_lowercase().chars().collect();
+    (0..chars.len())
+        .filter(|&start| {
+            chars[start].is_ascii_digit() && (start == 0 || !chars[start - 1].is_ascii_digit())
+        })
+        .find_map(|start| match_gestational_age(&chars, start))
+}
+
+/// Tenta reconhecer "semanas, separador, dias" a partir da posição `start`.
+fn match_gestational_age(chars: &[char], start: usize) -> Option<(i32, i32)> {
+    let read_number = |from: usize| -> Option<(i32, usize)> {
+        let end = (from..chars.len())
+            .find(|&i| !chars[i].is_ascii_digit())
+            .unwrap_or(chars.len());
+        let digits: String = chars[from..end].iter().collect();
+        Some((digits.parse().ok()?, end))
+    };
+    let skip_spaces = |from: usize| {
+        (from..chars.len())
+            .find(|&i| !chars[i].is_whitespace())
+            .unwrap_or(chars.len())
+    };
+
+    let (weeks, mut i) = read_number(start)?;
+    i = skip_spaces(i);
+    match chars.get(i)? {
+        '+' => i += 1,
+        // "s", "sem", "sem.", "semanas", "w"...: pula a palavra inteira.
+        's' | 'w' => {
+            while chars.get(i).is_some_and(|c| c.is_alphabetic() || *c == '.') {
+                i += 1;
+            }
+        }
+        _ => return None,
+    }
+    i = skip_spaces(i);
+    // Conector opcional: "32 semanas e 4 dias".
+    if chars.get(i) == Some(&'e') && chars.get(i + 1).is_some_and(|c| c.is_whitespace()) {
+        i = skip_spaces(i + 1);
+    }
+    let (days, _) = read_number(i)?;
+    (0..=6).contains(&days).then_some((weeks, days))
+}
+
+/// Classifica o nascimento pela idade gestacional ao nascer, em dias.
+fn preterm_category(gestational_age_days: i64) -> PretermCategory {
+    match gestational_age_days / 7 {
+        ..=27 => PretermCategory::Extreme,
+        28..=31 => PretermCategory::Very,
+        32..=36 => PretermCategory::ModerateToLate,
+        _ => PretermCategory::Term,
+    }
+}
+
+/// Compara a idade gestacional ao nascer informada (em dias) com a calculada a
+/// partir da data da última menstruação (DUM).
+fn check_lmp(lmp: NaiveDate, birthdate: NaiveDate, total_gestational_days: i64) -> LmpCheck {
+    if lmp > birthdate {
+        return LmpCheck::AfterBirth;
+    }
+    let lmp_gestational_days = birthdate.signed_duration_since(lmp).num_days();
+    let difference_days = lmp_gestational_days - total_gestational_days;
+    if difference_days.abs() <= LMP_TOLERANCE_DAYS {
+        LmpCheck::Consistent
+    } else {
+        LmpCheck::Discrepant {
+            lmp_gestational_days,
+            difference_days,
+        }
+    }
+}
+
+/// Resume um cálculo para o histórico: os dados informados e as idades principais.
+/// A idade gestacional é mostrada como digitada, para reconhecer intervalos e decimais.
+fn history_entry(report: &Report, weeks: &str, days: &str, lang: Lang) -> HistoryEntry {
+    let days = days.trim();
+    let gestational_age = if report.gestational_age_source == Some(GestationalAgeSource::Lmp) {
+        // Os campos digitados não foram usados: mostra a idade obtida da DUM.
+        let total_days = report.post_menstrual_days - (report.day_of_life - 1);
+        format!(
+            "{}s{}d ({})",
+            total_days / 7,
+            total_days % 7,
+            t(GestationalAgeSource::Lmp.label(), lang)
+        )
+    } else if days.is_empty() {
+        weeks.trim().to_string()
+    } else {
+        format!("{}s{}d", weeks.trim(), days)
+    };
+    HistoryEntry {
+        time: chrono::Local::now().format("%H:%M").to_string(),
+        inputs: tf(
+            "nascido em {} com {}, em {}",
+            lang,
+            &[
+                &report.birthdate.format(dates::DATE_FORMAT),
+                &gestational_age,
+                &report.today.format(dates::DATE_FORMAT),
+            ],
+        ),
+        result: tf(
+            "Cronológica: {} semanas; corrigida: {} semanas e {} dias",
+            lang,
+            &[
+                &report.chronological.total_weeks,
+                &report.corrected.weeks,
+                &report.corrected.days_in_week,
+            ],
+        ),
+    }
+}
+
+/// Rejeita um nascimento posterior à data de referência, que produziria idades
+/// negativas, ou mais de `MAX_AGE_YEARS` anteriores a ela, que produziria idades
+/// absurdas. Os dois casos são, em geral, erros de digitação no ano.
+fn validate_birthdate(birthdate: NaiveDate, today: NaiveDate, lang: Lang) -> Result<(), String> {
+    if birthdate > today {
+        return Err(t("Data de nascimento não pode ser futura.", lang).to_string());
+    }
+    if birthdate
+        .checked_add_months(Months::new(MAX_AGE_YEARS * 12))
+        .is_some_and(|limit| today > limit)
+    {
+        return Err(tf(
+            "A data de referência está a mais de {} anos do nascimento. Confira o ano digitado.",
+            lang,
+            &[&MAX_AGE_YEARS],
+        ));
+    }
+    Ok(())
+}
+
+/// Encontra o próximo "mesversário" em idade corrigida: a primeira data, a partir de
+/// `today` (inclusive), em que o bebê completa um número inteiro de meses corrigidos.
+fn next_corrected_month_birthday(
+    corrected_birthdate: NaiveDate,
+    today: NaiveDate,
+) -> Option<Checkpoint> {
+    // Limita a busca a 100 anos para nunca entrar em laço infinito.
+    (1..=1200).find_map(|months| {
+        let date = date_at_corrected_months(corrected_birthdate, months);
+        (date >= today).then_some(Checkpoint {
+            corrected_months: months,
+            date,
+        })
+    })
+}
+
+/// Semanas que faltam para a idade corrigida atingir `CORRECTION_CUTOFF_MONTHS`,
+/// arredondadas para cima. Só há valor para prematuros a até `CUTOFF_NOTICE_WEEKS`
+/// semanas do limite; a termo ou após o limite, a correção não é mais assunto.
+fn weeks_until_cutoff(report: &Report) -> Option<i64> {
+    if report.prematurity_days == 0 {
+        return None;
+    }
+    let cutoff = date_at_corrected_months(report.corrected_birthdate, CORRECTION_CUTOFF_MONTHS);
+    let days_left = cutoff.signed_duration_since(report.today).num_days();
+    let weeks_left = (days_left + 6) / 7;
+    (days_left > 0 && weeks_left <= CUTOFF_NOTICE_WEEKS).then_some(weeks_left)
+}
+
+/// Calcula as datas das consultas de seguimento padrão a partir da data de
+/// nascimento corrigida.
+fn calculate_checkpoints(corrected_birthdate: NaiveDate) -> Vec<Checkpoint> {
+    FOLLOW_UP_CHECKPOINTS_MONTHS
+        .iter()
+        .map(|&months| Checkpoint {
+            corrected_months: months,
+            date: date_at_corrected_months(corrected_birthdate, months),
+        })
+        .collect()
+}
+
+/// Testes de propriedades das funções de cálculo, com datas e idades gestacionais
+/// geradas aleatoriamente. Invariantes verificados, para qualquer nascimento até a
+/// data de hoje:
+/// - a idade corrigida nunca é maior que a cronológica;
+/// - a idade corrigida nunca é negativa (mesmo antes de o bebê completar 40 semanas);
+/// - `weeks * 7 + days_in_week` é exatamente o total de dias desde a data de
+///   nascimento corrigida (ou desde o nascimento, para bebês a termo);
+/// - a decomposição em anos, meses e dias é sempre não negativa, com meses < 12.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dates::date;
+    use proptest::prelude::*;
+
+    /// Gera (nascimento, hoje, semanas, dias) com hoje >= nascimento.
+    fn inputs() -> impl Strategy<Value = (NaiveDate, NaiveDate, i32, i32)> {
+        (
+            0i64..12_000,
+            0i64..3_000,
+            MIN_GESTATIONAL_WEEKS..=44,
+            0i32..=6,
+        )
+            .prop_map(|(birth_offset, age_days, weeks, days)| {
+                let birthdate = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap()
+                    + chrono::Duration::days(birth_offset);
+                let today = birthdate + chrono::Duration::days(age_days);
+                (birthdate, today, weeks, days)
+            })
+    }
+
+    /// Resultado com a contagem de meses pela duração média e o termo de 40 semanas.
+    fn report_for(birthdate: NaiveDate, weeks: i32, days: i32, today: NaiveDate) -> Report {
+        compute_all(
+            birthdate,
+            weeks,
+            days,
+            today,
+            MonthCount::AverageDays,
+            FULL_TERM_WEEKS,
+        )
+    }
+
+    /// Formulário preenchido, sem os dados salvos do usuário, calculado em 15/05/2024.
+    fn form(birth: &str, weeks: &str, days: &str) -> AgeCalculatorApp {
+        AgeCalculatorApp {
+            birth_date_str: birth.to_string(),
+            gestational_weeks_str: weeks.to_string(),
+            gestational_days_str: days.to_string(),
+            reference_date_str: "15/05/2024".to_string(),
+            ..AgeCalculatorApp::new(
+                LastInputs::default(),
+                Roster::default(),
+                Settings::default(),
+            )
+        }
+    }
+
+    #[test]
+    fn lmp_consistent_with_informed_gestational_age() {
+        // DUM 224 dias (32 semanas) antes do nascimento, informado 32s + 3d.
+        let birthdate = date(15, 3, 2023);
+        let lmp = birthdate - chrono::Duration::days(224);
+        assert_eq!(check_lmp(lmp, birthdate, 32 * 7 + 3), LmpCheck::Consistent);
+    }
+
+    #[test]
+    fn lmp_contradicting_informed_gestational_age() {
+        // DUM indica 36 semanas, mas foi informado 30 semanas: 42 dias de diferença.
+        let birthdate = date(15, 3, 2023);
+        let lmp = birthdate - chrono::Duration::days(36 * 7);
+        assert_eq!(
+            check_lmp(lmp, birthdate, 30 * 7),
+            LmpCheck::Discrepant {
+                lmp_gestational_days: 36 * 7,
+                difference_days: 42,
+            }
+        );
+    }
+
+    #[test]
+    fn gestational_age_source_is_the_first_valid_in_priority_order() {
+        use GestationalAgeSource::{Informed, Lmp};
+        let informed_first = GestationalAgePriority::InformedFirst;
+        let lmp_first = GestationalAgePriority::LmpFirst;
+        assert_eq!(
+            select_gestational_age_source(informed_first, true, true),
+            Some(Informed)
+        );
+        assert_eq!(
+            select_gestational_age_source(informed_first, false, true),
+            Some(Lmp)
+        );
+        assert_eq!(
+            select_gestational_age_source(lmp_first, true, true),
+            Some(Lmp)
+        );
+        assert_eq!(
+            select_gestational_age_source(lmp_first, true, false),
+            Some(Informed)
+        );
+        assert_eq!(select_gestational_age_source(lmp_first, false, false), None);
+    }
+
+    #[test]
+    fn lmp_first_priority_takes_the_gestational_age_from_the_lmp() {
+        // DUM 36 semanas antes do nascimento; informado 30 semanas.
+        let mut app = form("15/03/2023", "30", "");
+        app.lmp_str = "06/07/2022".to_string();
+        app.settings.gestational_age_priority = GestationalAgePriority::LmpFirst;
+        let inputs = app.parse_inputs().unwrap();
+        assert_eq!((inputs.gestational_weeks, inputs.gestational_days), (36, 0));
+        assert_eq!(inputs.gestational_age_source, GestationalAgeSource::Lmp);
+
+        // Sem semanas válidas, a DUM é usada mesmo com a prioridade padrão, e a
+        // fonte aparece nos detalhes.
+        app.settings.gestational_age_priority = GestationalAgePriority::InformedFirst;
+        app.gestational_weeks_str = String::new();
+        app.calculate();
+        let report = app.report.as_ref().unwrap();
+        assert_eq!(
+            report.gestational_age_source,
+            Some(GestationalAgeSource::Lmp)
+        );
+        assert!(detail_rows(report, Lang::PtBr)
+            .contains(&("Fonte da IG".to_string(), "DUM".to_string())));
+
+        // Uma DUM fora da faixa suportada não é usada: vale a idade informada.
+        app.gestational_weeks_str = "30".to_string();
+        app.lmp_str = "01/01/2022".to_string();
+        app.settings.gestational_age_priority = GestationalAgePriority::LmpFirst;
+        let inputs = app.parse_inputs().unwrap();
+        assert_eq!(inputs.gestational_weeks, 30);
+        assert_eq!(
+            inputs.gestational_age_source,
+            GestationalAgeSource::Informed
+        );
+    }
+
+    #[test]
+    fn corrected_months_and_days_use_calendar_months() {
+        // 32 semanas: termo em 26/02/2024; em 15/05/2024 são 79 dias corrigidos.
+        let report = report_for(date(1, 1, 2024), 32, 0, date(15, 5, 2024));
+        assert_eq!(
+            format_corrected_months_and_days(&report, Lang::PtBr),
+            "2 meses e 19 dias corrigidos"
+        );
+        // O formato por semanas decompõe os mesmos 79 dias de outra forma.
+        assert_eq!(report.corrected.weeks, 11);
+        assert_eq!(report.corrected.days_in_week, 2);
+        assert_eq!(report.corrected.total_months, 2);
+    }
+
+    #[test]
+    fn corrected_months_and_days_is_zero_before_term() {
+        let report = report_for(date(1, 1, 2024), 30, 0, date(1, 2, 2024));
+        assert_eq!(
+            format_corrected_months_and_days(&report, Lang::PtBr),
+            "0 meses e 0 dias corrigidos"
+        );
+    }
+
+    #[test]
+    fn three_and_four_days_are_half_a_week() {
+        assert_eq!(
+            format_weeks_with_fraction(8, 3, Lang::PtBr),
+            "8 semanas e meia"
+        );
+        assert_eq!(
+            format_weeks_with_fraction(8, 4, Lang::PtBr),
+            "8 semanas e meia"
+        );
+        assert_eq!(
+            format_weeks_with_fraction(1, 4, Lang::PtBr),
+            "1 semana e meia"
+        );
+    }
+
+    #[test]
+    fn uneven_days_fall_back_to_weeks_and_days() {
+        assert_eq!(
+            format_weeks_with_fraction(8, 1, Lang::PtBr),
+            "8 semanas e 1 dia"
+        );
+        assert_eq!(
+            format_weeks_with_fraction(8, 6, Lang::PtBr),
+            "8 semanas e 6 dias"
+        );
+        assert_eq!(format_weeks_with_fraction(8, 0, Lang::PtBr), "8 semanas");
+    }
+
+    #[test]
+    fn decimal_weeks_are_explained_in_integer_mode() {
+        let error = parse_gestational_weeks("32.5", false, Lang::PtBr).unwrap_err();
+        assert!(error.contains("casas decimais"), "{}", error);
+        assert!(parse_gestational_weeks("32,5", false, Lang::PtBr).is_err());
+        assert_eq!(
+            parse_gestational_weeks("abc", false, Lang::PtBr),
+            Err("Idade gestacional deve ser um número.".to_string())
+        );
+    }
+
+    #[test]
+    fn decimal_weeks_are_converted_in_decimal_mode() {
+        assert_eq!(
+            parse_gestational_weeks("32,5", true, Lang::PtBr),
+            Ok(WeeksInput::Decimal { weeks: 32, days: 4 })
+        );
+        assert_eq!(
+            parse_gestational_weeks("32.95", true, Lang::PtBr),
+            Ok(WeeksInput::Decimal { weeks: 33, days: 0 })
+        );
+        assert_eq!(
+            parse_gestational_weeks("32", true, Lang::PtBr),
+            Ok(WeeksInput::Single(32))
+        );
+    }
+
+    #[test]
+    fn enter_advances_to_the_next_field_by_default() {
+        let behavior = EnterBehavior::NextField;
+        assert_eq!(
+            enter_action(InputField::BirthDate, behavior, true),
+            EnterAction::Focus(InputField::Weeks)
+        );
+        assert_eq!(
+            enter_action(InputField::Weeks, behavior, true),
+            EnterAction::Focus(InputField::Days)
+        );
+        assert_eq!(
+            enter_action(InputField::Days, behavior, false),
+            EnterAction::Calculate
+        );
+    }
+
+    #[test]
+    fn enter_calculates_from_any_field_when_inputs_are_valid() {
+        let behavior = EnterBehavior::Calculate;
+        assert_eq!(
+            enter_action(InputField::BirthDate, behavior, true),
+            EnterAction::Calculate
+        );
+        assert_eq!(
+            enter_action(InputField::Weeks, behavior, true),
+            EnterAction::Calculate
+        );
+        // Com entradas inválidas, continua avançando para o próximo campo.
+        assert_eq!(
+            enter_action(InputField::BirthDate, behavior, false),
+            EnterAction::Focus(InputField::Weeks)
+        );
+    }
+
+    #[test]
+    fn reference_dates_are_sorted_and_invalid_entries_reported() {
+        let (valid, invalid) = parse_reference_dates(
+            "15/05/2024\n01/03/2024; 31/02/2024, 01/03/2024 abc",
+            date(15, 10, 2026),
+        );
+        assert_eq!(valid, vec![date(1, 3, 2024), date(15, 5, 2024)]);
+        assert_eq!(invalid, vec!["31/02/2024", "abc"]);
+    }
+
+    #[test]
+    fn gestational_age_is_extracted_from_pasted_text() {
+        for pasted in [
+            "32+4",
+            "IG 32+4 sem",
+            "IG: 32 + 4 semanas (USG)",
+            "32s4d",
+            "32 sem 4 d",
+            "Idade gestacional: 32 semanas e 4 dias",
+            "GA 32w 4d",
+        ] {
+            assert_eq!(extract_gestational_age(pasted), Some((32, 4)), "{}", pasted);
+        }
+        assert_eq!(
+            parse_gestational_weeks("IG 32+4 sem", false, Lang::PtBr),
+            Ok(WeeksInput::Combined { weeks: 32, days: 4 })
+        );
+    }
+
+    #[test]
+    fn text_without_weeks_and_days_is_rejected() {
+        for pasted in ["32", "IG 32 sem", "31-33", "32+9", "USG 12/03/2024", ""] {
+            assert_eq!(extract_gestational_age(pasted), None, "{}", pasted);
+        }
+        assert_eq!(
+            parse_gestational_weeks("IG sem dados", false, Lang::PtBr),
+            Err("Idade gestacional deve ser um número.".to_string())
+        );
+    }
+
+    #[test]
+    fn cutoff_notice_appears_only_near_the_cutoff() {
+        // 32 semanas: termo em 26/02/2024, 24 meses corrigidos em 26/02/2026.
+        let birthdate = date(1, 1, 2024);
+        let report = report_for(birthdate, 32, 0, date(15, 1, 2026));
+        assert_eq!(weeks_until_cutoff(&report), Some(6));
+        let report = report_for(birthdate, 32, 0, date(25, 2, 2026));
+        assert_eq!(weeks_until_cutoff(&report), Some(1));
+        // Longe do limite, no dia do limite e depois dele, não há aviso.
+        for today in [date(1, 6, 2025), date(26, 2, 2026), date(1, 3, 2026)] {
+            let report = report_for(birthdate, 32, 0, today);
+            assert_eq!(weeks_until_cutoff(&report), None);
+        }
+    }
+
+    /// Confere que nenhuma linha do resultado exibe um número negativo.
+    fn assert_no_negative_values(report: &Report) {
+        let rows = result_rows(report, AgeConvention::International, Lang::PtBr)
+            .into_iter()
+            .chain(detail_rows(report, Lang::PtBr));
+        for (label, value) in rows {
+            let negative = value
+                .chars()
+                .zip(value.chars().skip(1))
+                .any(|(sign, digit)| sign == '-' && digit.is_ascii_digit());
+            assert!(!negative, "{}: {}", label, value);
+        }
+    }
+
+    #[test]
+    fn very_preterm_baby_before_term_date_shows_zero_corrected_age() {
+        // 24 semanas: o termo é 16 semanas depois do nascimento.
+        let report = report_for(date(1, 1, 2024), 24, 0, date(1, 2, 2024));
+        assert_no_negative_values(&report);
+        assert_eq!(
+            (report.corrected.weeks, report.corrected.total_months),
+            (0, 0)
+        );
+        assert_eq!(
+            format_corrected_months_and_days(&report, Lang::PtBr),
+            "0 meses e 0 dias corrigidos"
+        );
+        assert_eq!(weeks_until_cutoff(&report), None);
+    }
+
+    #[test]
+    fn very_preterm_baby_past_the_cutoff_has_coherent_ages() {
+        let report = report_for(date(1, 1, 2024), 24, 0, date(1, 6, 2026));
+        assert_no_negative_values(&report);
+        assert_eq!(weeks_until_cutoff(&report), None);
+        assert_eq!(report.corrected.years, 2);
+        assert!(report.corrected.total_months >= CORRECTION_CUTOFF_MONTHS as i64);
+        assert!(report.corrected.total_months < report.chronological.total_months);
+    }
+
+    #[test]
+    fn term_baby_has_no_correction_in_any_branch() {
+        let report = report_for(date(1, 1, 2024), 40, 0, date(1, 6, 2025));
+        assert_no_negative_values(&report);
+        assert_eq!(weeks_until_cutoff(&report), None);
+        assert_eq!(
+            report.corrected.weeks * 7 + report.corrected.days_in_week,
+            report.day_of_life - 1
+        );
+        assert_eq!(
+            format_corrected_months_and_days(&report, Lang::PtBr),
+            "17 meses e 0 dias corrigidos"
+        );
+    }
+
+    #[test]
+    fn cutoff_notice_is_hidden_for_term_babies() {
+        let report = report_for(date(1, 1, 2024), 40, 0, date(15, 12, 2025));
+        assert_eq!(weeks_until_cutoff(&report), None);
+    }
+
+    #[test]
+    fn padded_inputs_are_trimmed_before_parsing() {
+        let mut app = form(" 15/03/2023 ", " 32 ", "\t4 ");
+        app.reference_date_str = " 15/05/2024\n".to_string();
+        let inputs = app.parse_inputs().unwrap();
+        assert_eq!(inputs.birthdate, date(15, 3, 2023));
+        assert_eq!(inputs.gestational_weeks, 32);
+        assert_eq!(inputs.gestational_days, 4);
+        assert_eq!(inputs.reference_date, Some(date(15, 5, 2024)));
+
+        app.calculate();
+        assert_eq!(app.error_message, None);
+        assert!(app.report.is_some());
+    }
+
+    #[test]
+    fn periviable_gestations_are_calculated_with_a_warning() {
+        for (weeks, days) in [("22", "0"), ("23", "6")] {
+            let mut app = form("01/01/2024", weeks, days);
+            app.calculate();
+            assert!(app.report.is_some(), "{}s{}d", weeks, days);
+            assert_eq!(app.error_message, None);
+            assert!(app
+                .warning_message
+                .as_deref()
+                .is_some_and(|warning| warning.contains("limite da viabilidade")));
+        }
+
+        let mut app = form("01/01/2024", "24", "0");
+        app.calculate();
+        assert!(app.report.is_some());
+        assert_eq!(app.warning_message, None);
+    }
+
+    #[test]
+    fn future_birthdate_is_rejected() {
+        let today = date(15, 10, 2026);
+        assert_eq!(validate_birthdate(today, today, Lang::PtBr), Ok(()));
+        assert_eq!(
+            validate_birthdate(date(14, 10, 2026), today, Lang::PtBr),
+            Ok(())
+        );
+        assert_eq!(
+            validate_birthdate(date(16, 10, 2026), today, Lang::PtBr),
+            Err("Data de nascimento não pode ser futura.".to_string())
+        );
+    }
+
+    #[test]
+    fn gestational_weeks_outside_the_plausible_range_are_rejected() {
+        for weeks in [MIN_GESTATIONAL_WEEKS, 32, MAX_GESTATIONAL_WEEKS] {
+            assert_eq!(validate_gestational_weeks(weeks, Lang::PtBr), Ok(()));
+        }
+        for weeks in [
+            -5,
+            0,
+            MIN_GESTATIONAL_WEEKS - 1,
+            MAX_GESTATIONAL_WEEKS + 1,
+            400,
+        ] {
+            assert!(
+                validate_gestational_weeks(weeks, Lang::PtBr).is_err(),
+                "{}",
+                weeks
+            );
+        }
+    }
+
+    #[test]
+    fn out_of_range_gestational_weeks_give_an_error_and_no_result() {
+        let above = "Idade gestacional máxima suportada é de 45 semanas. Confira o valor digitado.";
+        let below = "Idade gestacional mínima suportada é de 22 semanas.";
+        for (weeks, message) in [("400", above), ("-5", below), ("21", below)] {
+            let mut app = form("01/01/2024", weeks, "0");
+            app.calculate();
+            assert_eq!(app.error_message.as_deref(), Some(message), "{}", weeks);
+            assert!(app.report.is_none(), "{}", weeks);
+        }
+    }
+
+    #[test]
+    fn gestational_days_must_be_within_the_week() {
+        assert_eq!(parse_gestational_days("0", Lang::PtBr), Ok(0));
+        assert_eq!(parse_gestational_days(" 6 ", Lang::PtBr), Ok(6));
+        for input in ["7", "9", "-1"] {
+            assert_eq!(
+                parse_gestational_days(input, Lang::PtBr),
+                Err("Dias devem estar entre 0 e 6.".to_string())
+            );
+        }
+        assert!(parse_gestational_days("quatro", Lang::PtBr).is_err());
+    }
+
+    #[test]
+    fn blank_days_count_as_zero() {
+        assert_eq!(parse_gestational_days("", Lang::PtBr), Ok(0));
+        assert_eq!(parse_gestational_days("  ", Lang::PtBr), Ok(0));
+        assert_eq!(
+            parse_gestational_days("x", Lang::PtBr),
+            Err("Dias na semana de nascimento devem ser um número.".to_string())
+        );
+    }
+
+    #[test]
+    fn implausibly_distant_reference_date_is_rejected() {
+        let birthdate = date(1, 1, 2024);
+        assert_eq!(
+            validate_birthdate(birthdate, date(1, 1, 2174), Lang::PtBr),
+            Ok(())
+        );
+        assert!(validate_birthdate(birthdate, date(2, 1, 2174), Lang::PtBr).is_err());
+        assert!(validate_birthdate(birthdate, date(1, 1, 3000), Lang::PtBr)
+            .unwrap_err()
+            .contains("150 anos"));
+    }
+
+    #[test]
+    fn history_entry_summarizes_inputs_and_result() {
+        let report = report_for(date(1, 1, 2024), 32, 4, date(15, 5, 2024));
+        let entry = history_entry(&report, " 32 ", "4", Lang::PtBr);
+        assert_eq!(
+            entry.inputs,
+            "nascido em 01/01/2024 com 32s4d, em 15/05/2024"
+        );
+        assert_eq!(
+            entry.result,
+            "Cronológica: 19 semanas; corrigida: 11 semanas e 6 dias"
+        );
+        assert_eq!(
+            history_entry(&report, "32+4", "", Lang::PtBr).inputs,
+            "nascido em 01/01/2024 com 32+4, em 15/05/2024"
+        );
+    }
+
+    #[test]
+    fn pdf_starts_with_the_calculation_date_and_inputs() {
+        let report = report_for(date(1, 1, 2024), 32, 4, date(15, 5, 2024));
+        let lines = pdf_lines(&report, &Settings::default());
+        assert_eq!(lines[0].text, "Calculado em 15/05/2024");
+        assert_eq!(lines[2].text, "Data de nascimento: 01/01/2024");
+        assert_eq!(
+            lines[3].text,
+            "Idade gestacional ao nascer: 32 semanas e 4 dias"
+        );
+    }
+
+    #[test]
+    fn long_pdf_exports_continue_on_more_pages() {
+        // Com as fórmulas, os detalhes, as consultas e um observador longo, quebrado
+        // em várias linhas, o texto passa de uma página, e nenhuma linha se perde.
+        let mut report = report_for(date(1, 1, 2024), 32, 4, date(15, 5, 2024));
+        report.observer = Some("USG do primeiro trimestre, conferida pela equipe. ".repeat(20));
+        let settings = Settings {
+            show_formulas: true,
+            ..Settings::default()
         };
+        let lines = pdf_lines(&report, &settings);
+        // As linhas caberiam em uma página; é a quebra do observador que a ultrapassa.
+        assert!(lines.len() <= pdf::LINES_PER_PAGE, "{}", lines.len());
+
+        let document = pdf::document("Resultado", &lines, false);
+        let text = String::from_utf8_lossy(&document);
+        assert!(text.contains("/Count 2 >>"));
+        assert_eq!(text.matches("(Resultado) '").count(), 2);
+        let last = lines.last().unwrap().text.as_str();
+        assert!(last.is_ascii(), "{}", last);
+        assert!(text.contains(&format!("({}) '", last)), "{}", last);
+    }
+
+    #[test]
+    fn html_fragment_has_one_table_row_per_result_row() {
+        let mut report = report_for(date(1, 1, 2024), 32, 4, date(15, 5, 2024));
+        report.observer = Some("Dra. <Ana> & equipe".to_string());
+        let settings = Settings::default();
+        let html = format_result_html(&report, &settings);
+
+        // Três tabelas: idades, detalhes e consultas de seguimento.
+        assert!(html.starts_with("<table>\n"));
+        assert_eq!(html.matches("<table>").count(), 3);
+        assert_eq!(html.matches("</table>").count(), 3);
+
+        let ages = html.split("</table>").next().unwrap();
+        let rows = result_rows(&report, settings.age_convention, Lang::PtBr);
+        assert_eq!(ages.matches("<tr>").count(), rows.len());
+        for (label, value) in &rows {
+            assert!(ages.contains(&format!("<tr><th>{}</th><td>{}</td></tr>", label, value)));
+        }
+
+        // O texto livre do observador é escapado.
+        assert!(html
+            .contains("<tr><th>Observador/Fonte</th><td>Dra. &lt;Ana&gt; &amp; equipe</td></tr>"));
+        assert!(!html.contains("<Ana>"));
+    }
+
+    #[test]
+    fn help_texts_follow_the_correction_term() {
+        let weeks = CorrectionTerm::Weeks37.weeks();
+        assert_eq!(
+            tf(WEEKS_HELP, Lang::PtBr, &[&weeks]),
+            "Semanas completas de gestação ao nascer, conforme o obstetra (37 é o termo)."
+        );
+        assert!(tf(CORRECTED_AGE_HELP, Lang::PtBr, &[&weeks]).contains("para as 37 semanas"));
+        assert!(tf(CORRECTED_AGE_HELP, Lang::En, &[&weeks]).contains("short of 37 weeks"));
+    }
+
+    #[test]
+    fn non_default_correction_term_is_indicated() {
+        let mut settings = Settings::default();
+        assert_eq!(term_indicator(&settings), None);
+        settings.show_default_term = true;
+        assert_eq!(
+            term_indicator(&settings).as_deref(),
+            Some("Termo de referência: 40 semanas")
+        );
+
+        settings.show_default_term = false;
+        settings.correction_term = CorrectionTerm::Weeks37;
+        assert_eq!(
+            term_indicator(&settings).as_deref(),
+            Some("Termo de referência: 37 semanas")
+        );
+        settings.language = Lang::En;
+        assert_eq!(
+            term_indicator(&settings).as_deref(),
+            Some("Reference term: 37 weeks")
+        );
+    }
+
+    #[test]
+    fn glance_title_shows_the_corrected_age_of_today() {
+        // Calculado em 15/05/2024 (11 semanas e 6 dias corrigidos), visto uma semana depois.
+        let report = report_for(date(1, 1, 2024), 32, 4, date(15, 5, 2024));
+        assert_eq!(
+            glance_title(&report, None, date(22, 5, 2024), Lang::PtBr),
+            "Idade corrigida hoje: 12 semanas e 6 dias"
+        );
+        assert_eq!(
+            glance_title(&report, Some("Ana"), date(15, 5, 2024), Lang::PtBr),
+            "Ana – Idade corrigida hoje: 11 semanas e 6 dias"
+        );
+    }
+
+    #[test]
+    fn reference_date_shortcuts_count_back_from_today() {
+        let today = date(5, 3, 2024);
+        let filled: Vec<String> = REFERENCE_DATE_SHORTCUTS
+            .iter()
+            .map(|&(_, days)| days_before_today(today, days))
+            .collect();
+        assert_eq!(filled, ["05/03/2024", "27/02/2024", "04/02/2024"]);
+    }
+
+    #[test]
+    fn decimal_weeks_have_one_decimal_place() {
+        // 136 dias são 19,428... semanas.
+        assert_eq!(format_decimal_weeks(136, Lang::PtBr), "19,4");
+        assert_eq!(format_decimal_weeks(136, Lang::En), "19.4");
+        assert_eq!(format_decimal_weeks(70, Lang::PtBr), "10,0");
+        assert_eq!(format_decimal_weeks(4, Lang::PtBr), "0,6");
+
+        let report = report_for(date(1, 1, 2024), 32, 4, date(15, 5, 2024));
+        let rows = result_rows(&report, AgeConvention::International, Lang::PtBr);
+        assert!(rows.contains(&(
+            "Idades em Semanas Decimais".to_string(),
+            "cronológica 19,3; corrigida 11,9".to_string()
+        )));
+    }
+
+    #[test]
+    fn sex_is_exported_and_gates_the_weight_percentile() {
+        let mut report = report_for(date(1, 1, 2024), 32, 0, date(1, 1, 2024));
+        report.weight_grams = Some(1750.0);
+        let export = format_export_text(&report, &Settings::default());
+        assert!(export.contains("Sexo: Não informado"));
+        assert_eq!(
+            weight_percentile_text(&report, Lang::PtBr).unwrap(),
+            "Sexo não informado: o percentil do peso não pode ser calculado."
+        );
+
+        report.sex = Some(growth::Sex::Male);
+        assert!(format_export_text(&report, &Settings::default()).contains("Sexo: Masculino"));
+        assert_eq!(
+            weight_percentile_text(&report, Lang::PtBr).unwrap(),
+            "Peso no percentil ~50 (Fenton)"
+        );
+    }
+
+    #[test]
+    fn each_twin_gets_a_labeled_block() {
+        let baby_1 = report_for(date(1, 1, 2024), 32, 4, date(15, 5, 2024));
+        let baby_2 = report_for(date(2, 1, 2024), 32, 4, date(15, 5, 2024));
+        let format = |report: &Report| report.day_of_life.to_string();
+
+        assert_eq!(format_babies_text(&baby_1, &[], Lang::PtBr, format), "136");
+        assert_eq!(
+            format_babies_text(&baby_1, &[baby_2], Lang::PtBr, format),
+            "=== Bebê 1 (nascimento em 01/01/2024) ===\n136\n\n\
+             === Bebê 2 (nascimento em 02/01/2024) ===\n135"
+        );
+    }
+
+    #[test]
+    fn exports_of_twins_have_one_block_per_baby() {
+        let baby_1 = report_for(date(1, 1, 2024), 32, 4, date(15, 5, 2024));
+        let siblings = [report_for(date(2, 1, 2024), 32, 4, date(15, 5, 2024))];
+        let settings = Settings::default();
+        let export = |format| export_contents(format, &baby_1, &siblings, &settings);
+        let headings = [
+            "Bebê 1 (nascimento em 01/01/2024)",
+            "Bebê 2 (nascimento em 02/01/2024)",
+        ];
+
+        let text = String::from_utf8(export(ExportFormat::Text)).unwrap();
+        for heading in headings {
+            assert_eq!(text.matches(&format!("=== {} ===", heading)).count(), 1);
+        }
+        assert_eq!(text.matches("Dia de Vida: ").count(), 2);
+
+        let html = format_babies_html(&baby_1, &siblings, &settings);
+        for heading in headings {
+            assert!(html.contains(&format!("<h3>{}</h3>\n<table>", heading)));
+        }
+        assert_eq!(html.matches("<table>").count(), 6);
+
+        let csv = String::from_utf8(export(ExportFormat::Csv)).unwrap();
+        assert_eq!(
+            csv,
+            "15/05/2024;01/01/2024;32;4;19;11;0;2;23;1\n\
+             15/05/2024;02/01/2024;32;4;19;11;0;2;22;2\n"
+        );
+
+        let fhir: serde_json::Value = serde_json::from_slice(&export(ExportFormat::Fhir)).unwrap();
+        let observations = fhir.as_array().unwrap();
+        let metrics = report_metrics(&baby_1).len();
+        assert_eq!(observations.len(), 2 * metrics);
+        assert_eq!(observations[0]["subject"]["display"], "Bebê 1");
+        assert_eq!(observations[metrics]["subject"]["display"], "Bebê 2");
+
+        let pdf = export(ExportFormat::Pdf);
+        let contains = |needle: &[u8]| pdf.windows(needle.len()).any(|w| w == needle);
+        assert!(contains(b"(Beb\xea 1 \\(nascimento em 01/01/2024\\)) '"));
+        assert!(contains(b"(Beb\xea 2 \\(nascimento em 02/01/2024\\)) '"));
+
+        // Com um bebê só, não há títulos nem número do bebê.
+        let single = export_contents(ExportFormat::Csv, &baby_1, &[], &settings);
+        assert!(String::from_utf8(single).unwrap().ends_with(";23;\n"));
+        assert!(!format_babies_html(&baby_1, &[], &settings).contains("<h3>"));
+    }
+
+    #[test]
+    fn twin_session_is_restored_on_the_next_launch() {
+        let mut app = form("01/01/2024", "32", "4");
+        app.babies = 2;
+        app.sibling_birth_dates_str = ["02/01/2024".to_string(), "05/01/2024".to_string()];
+        app.sex = Some(growth::Sex::Male);
+
+        // Gravado e lido de novo como no arquivo de últimos valores.
+        let json = serde_json::to_string(&app.last_inputs()).unwrap();
+        let restored = AgeCalculatorApp::new(
+            serde_json::from_str(&json).unwrap(),
+            Roster::default(),
+            Settings::default(),
+        );
+        assert_eq!(restored.babies, 2);
+        // Só o Bebê 2 faz parte da sessão; o campo escondido do Bebê 3 não volta.
+        assert_eq!(
+            restored.sibling_birth_dates_str,
+            ["02/01/2024".to_string(), String::new()]
+        );
+        assert_eq!(restored.birth_date_str, "01/01/2024");
+        assert_eq!(restored.gestational_weeks_str, "32");
+        assert_eq!(restored.sex, Some(growth::Sex::Male));
+    }
+
+    #[test]
+    fn month_count_setting_changes_the_total_months() {
+        // Nascido a termo em 31/01/2024: em 30/04/2024, 3 meses de calendário e 90 dias.
+        let average = report_for(date(31, 1, 2024), 40, 0, date(30, 4, 2024));
+        let calendar = compute_all(
+            date(31, 1, 2024),
+            40,
+            0,
+            date(30, 4, 2024),
+            MonthCount::Calendar,
+            FULL_TERM_WEEKS,
+        );
+        assert_eq!(average.chronological.total_months, 2);
+        assert_eq!(calendar.chronological.total_months, 3);
+        assert_eq!(calendar.corrected.total_months, 3);
+        assert_eq!(
+            result_rows(&calendar, AgeConvention::International, Lang::PtBr)[0].1,
+            "12 semanas (3 meses) e 6 dias"
+        );
+    }
+
+    #[test]
+    fn pdf_highlights_the_preterm_classification_only() {
+        let report = report_for(date(1, 1, 2024), 32, 4, date(15, 5, 2024));
+        let lines = pdf_lines(&report, &Settings::default());
+        assert_eq!(
+            lines[4].text,
+            "Classificação: Prematuro moderado a tardio (32 a 36 semanas)"
+        );
+        assert_eq!(lines[4].highlight, Some([140, 110, 0]));
+        assert_eq!(
+            lines.iter().filter(|line| line.highlight.is_some()).count(),
+            1
+        );
+
+        let report = report_for(date(1, 1, 2024), 39, 0, date(15, 5, 2024));
+        let lines = pdf_lines(&report, &Settings::default());
+        assert!(lines.iter().all(|line| line.highlight.is_none()));
+    }
+
+    #[test]
+    fn complete_mode_shows_the_three_reference_frames() {
+        // 32s4d, termo em 22/02/2024; em 15/05/2024, 135 dias de vida.
+        let report = report_for(date(1, 1, 2024), 32, 4, date(15, 5, 2024));
+        assert_eq!(
+            format_complete_text(&report, Lang::PtBr),
+            "Desde o nascimento (cronológica): 19 semanas e 2 dias | 4 meses e 14 dias\n\
+             Desde o termo (corrigida): 11 semanas e 6 dias | 2 meses e 23 dias\n\
+             Pós-menstrual (IG ao nascer + vida): 51 semanas e 6 dias"
+        );
+    }
+
+    #[test]
+    fn csv_export_appends_rows_after_a_single_header() {
+        let path = std::env::temp_dir().join(format!(
+            "calculadora_acompanhamento_{}.csv",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        for today in [date(15, 5, 2024), date(15, 6, 2024)] {
+            let report = report_for(date(1, 1, 2024), 32, 4, today);
+            append_csv_row(&path, csv_row(&report, None).as_bytes()).unwrap();
+        }
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(lines[1], "15/05/2024;01/01/2024;32;4;19;11;0;2;23;");
+        assert!(lines[2].starts_with("15/06/2024;"));
+    }
+
+    #[test]
+    fn lmp_after_birth_is_rejected() {
+        assert_eq!(
+            check_lmp(date(20, 3, 2023), date(15, 3, 2023), 32 * 7),
+            LmpCheck::AfterBirth
+        );
     }
 
-    // Calcula a data de nascimento corrigida.
-    let corrected_birthdate = birthdate + chrono::Duration::days(prematurity_days as i64);
-    let corrected_age_as_chrono = calculate_chronological_age(corrected_birthdate, today);
-    let corrected_total_days = today
-        .signed_duration_since(corrected_birthdate)
-        .num_days()
-        .max(0);
-    let corrected_weeks = corrected_total_days / 7;
-    let corrected_days_in_week = corrected_total_days % 7;
-    let corrected_total_months = (corrected_total_days as f64 / 30.4375).floor() as i64;
-
-    CorrectedAge {
-        years: corrected_age_as_chrono.years,
-        months: corrected_age_as_chrono.months,
-        days: corrected_age_as_chrono.days,
-        weeks: corrected_weeks,
-        days_in_week: corrected_days_in_week,
-        total_months: corrected_total_months,
+    proptest! {
+        #[test]
+        fn corrected_age_never_exceeds_chronological_age(
+            (birthdate, today, weeks, days) in inputs()
+        ) {
+            let total_days = today.signed_duration_since(birthdate).num_days();
+            let corrected = calculate_corrected_age(birthdate, today, weeks, days, FULL_TERM_WEEKS);
+            prop_assert!(corrected.weeks * 7 + corrected.days_in_week <= total_days);
+        }
+
+        #[test]
+        fn corrected_age_is_never_negative((birthdate, today, weeks, days) in inputs()) {
+            let corrected = calculate_corrected_age(birthdate, today, weeks, days, FULL_TERM_WEEKS);
+            prop_assert!(corrected.weeks >= 0);
+            prop_assert!((0..7).contains(&corrected.days_in_week));
+        }
+
+        #[test]
+        fn corrected_weeks_and_days_add_up_to_total_days(
+            (birthdate, today, weeks, days) in inputs()
+        ) {
+            let corrected_birthdate = calculate_corrected_birthdate(birthdate, weeks, days, FULL_TERM_WEEKS);
+            let corrected_total_days = today
+                .signed_duration_since(corrected_birthdate)
+                .num_days()
+                .max(0);
+            let corrected = calculate_corrected_age(birthdate, today, weeks, days, FULL_TERM_WEEKS);
+            prop_assert_eq!(
+                corrected.weeks * 7 + corrected.days_in_week,
+                corrected_total_days
+            );
+        }
+
+        #[test]
+        fn sms_summary_fits_in_one_message((birthdate, today, weeks, days) in inputs()) {
+            let report = report_for(birthdate, weeks, days, today);
+            prop_assert!(format_sms_summary(&report, Lang::PtBr).chars().count() <= SMS_MAX_CHARS);
+        }
+
+        #[test]
+        fn chronological_breakdown_is_well_formed((birthdate, today, _weeks, _days) in inputs()) {
+            let age = calculate_chronological_age(birthdate, today);
+            prop_assert!(age.years >= 0);
+            prop_assert!((0..12).contains(&age.months));
+            prop_assert!(age.days >= 0);
+            prop_assert_eq!(
+                age.total_weeks,
+                today.signed_duration_since(birthdate).num_days() / 7
+            );
+        }
     }
 }
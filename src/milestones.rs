@@ -0,0 +1,47 @@
+// Marcos do desenvolvimento esperados em cada idade corrigida das consultas de
+// seguimento, como referência para as famílias. Os textos ficam todos nesta tabela
+// para facilitar a revisão e a tradução.
+
+/// Marcos de referência para uma idade corrigida.
+pub struct Milestone {
+    pub corrected_months: u32,
+    pub motor: &'static str,
+    pub language: &'static str,
+}
+
+/// Marcos esperados nas idades das consultas de seguimento (a maioria das crianças
+/// os atinge até essa idade corrigida).
+const MILESTONES: &[Milestone] = &[
+    Milestone {
+        corrected_months: 4,
+        motor: "Sustenta a cabeça sem apoio; leva as mãos à boca.",
+        language: "Ri e balbucia; vira a cabeça na direção de sons.",
+    },
+    Milestone {
+        corrected_months: 8,
+        motor: "Senta sem apoio; passa objetos de uma mão para a outra.",
+        language: "Repete sílabas (\"mamama\", \"papapa\"); reage ao próprio nome.",
+    },
+    Milestone {
+        corrected_months: 12,
+        motor: "Fica em pé com apoio e dá passos segurando-se nos móveis.",
+        language: "Fala uma ou duas palavras com significado; entende pedidos simples.",
+    },
+    Milestone {
+        corrected_months: 18,
+        motor: "Anda sozinho; sobe degraus com ajuda.",
+        language: "Fala várias palavras; aponta para mostrar o que quer.",
+    },
+    Milestone {
+        corrected_months: 24,
+        motor: "Corre; chuta uma bola.",
+        language: "Junta duas palavras em frases curtas; nomeia objetos e figuras.",
+    },
+];
+
+/// Marcos de referência para uma idade corrigida, se houver.
+pub fn for_corrected_months(corrected_months: u32) -> Option<&'static Milestone> {
+    MILESTONES
+        .iter()
+        .find(|milestone| milestone.corrected_months == corrected_months)
+}
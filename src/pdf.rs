@@ -0,0 +1,296 @@
+// Geração de um documento PDF com texto simples, para imprimir ou anexar o resultado
+// ao prontuário. O arquivo é montado diretamente (PDF 1.4, fonte Helvetica padrão
+// com codificação WinAnsi), sem depender de bibliotecas externas: o documento é só
+// texto em uma fonte padrão, e uma biblioteca como a 'printpdf' traria cerca de 30
+// dependências e 1,5 MB ao executável (também no WebAssembly) para isso.
+//
+// As linhas destacadas saem na cor indicada ou, no documento em preto e branco
+// (para impressoras monocromáticas), em negrito e precedidas de '»', para que
+// continuem se distinguindo sem a cor.
+
+/// Largura e altura de uma folha A4, em pontos.
+const PAGE_SIZE: (f32, f32) = (595.0, 842.0);
+/// Margem esquerda e superior, em pontos.
+const MARGIN: f32 = 56.0;
+/// Tamanho da fonte do título e do texto, e a distância entre as linhas.
+const TITLE_SIZE: f32 = 14.0;
+const FONT_SIZE: f32 = 10.0;
+const LEADING: f32 = 14.0;
+
+/// Marcador das linhas destacadas no documento em preto e branco.
+const MONOCHROME_MARKER: &str = "» ";
+
+/// Uma linha de texto do documento, com a cor do destaque quando ela merece atenção.
+pub struct Line {
+    pub text: String,
+    pub highlight: Option<[u8; 3]>,
+}
+
+impl From<String> for Line {
+    fn from(text: String) -> Self {
+        Line {
+            text,
+            highlight: None,
+        }
+    }
+}
+
+/// Quantos caracteres cabem em uma linha, pela largura média de um caractere da
+/// Helvetica (pouco mais de meio corpo). Linhas mais longas são quebradas.
+const MAX_CHARS: usize = ((PAGE_SIZE.0 - 2.0 * MARGIN) / (FONT_SIZE * 0.55)) as usize;
+
+/// Quantas linhas de texto cabem em cada página abaixo do título.
+pub const LINES_PER_PAGE: usize = ((PAGE_SIZE.1 - 2.0 * MARGIN - 2.0 * LEADING) / LEADING) as usize;
+
+/// Monta um PDF com o título em destaque e as linhas abaixo dele. As linhas que não
+/// couberem em uma página continuam nas seguintes, que repetem o título, e as mais
+/// largas que a página são quebradas entre as palavras. Com `monochrome`, os
+/// destaques usam negrito e um marcador em vez de cor.
+pub fn document(title: &str, lines: &[Line], monochrome: bool) -> Vec<u8> {
+    let lines: Vec<Line> = lines
+        .iter()
+        .flat_map(|line| {
+            wrap(&line.text, MAX_CHARS).into_iter().map(|text| Line {
+                text,
+                highlight: line.highlight,
+            })
+        })
+        .collect();
+    let pages: Vec<&[Line]> = if lines.is_empty() {
+        vec![&[]]
+    } else {
+        lines.chunks(LINES_PER_PAGE).collect()
+    };
+
+    // Objetos fixos (catálogo, lista de páginas e fontes), seguidos de cada página
+    // e do seu conteúdo.
+    let first_page = 5;
+    let kids = (0..pages.len())
+        .map(|index| format!("{} 0 R", first_page + 2 * index))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids, pages.len()).into_bytes(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+    ];
+    for (index, page_lines) in pages.into_iter().enumerate() {
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                 /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                PAGE_SIZE.0,
+                PAGE_SIZE.1,
+                first_page + 2 * index + 1
+            )
+            .into_bytes(),
+        );
+        let content = page_content(title, page_lines, monochrome);
+        let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+        stream.extend(content);
+        stream.extend_from_slice(b"endstream");
+        objects.push(stream);
+    }
+
+    // Cada objeto é gravado com sua posição no arquivo, que vai para a tabela 'xref'.
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::new();
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+        pdf.extend_from_slice(object);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+    let xref_offset = pdf.len();
+    pdf.extend_from_slice(
+        format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+    );
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref_offset
+        )
+        .as_bytes(),
+    );
+    pdf
+}
+
+/// Conteúdo de uma página: o título e até `LINES_PER_PAGE` linhas.
+fn page_content(title: &str, lines: &[Line], monochrome: bool) -> Vec<u8> {
+    let mut content = Vec::new();
+    content.extend_from_slice(
+        format!(
+            "BT\n/F1 {} Tf\n{} TL\n{} {} Td\n",
+            TITLE_SIZE,
+            LEADING,
+            MARGIN,
+            PAGE_SIZE.1 - MARGIN
+        )
+        .as_bytes(),
+    );
+    content.extend(text_operator(title));
+    content.extend_from_slice(format!("/F1 {} Tf\nT*\n", FONT_SIZE).as_bytes());
+    for line in lines {
+        match line.highlight {
+            None => content.extend(text_operator(&line.text)),
+            Some(_) if monochrome => {
+                content.extend_from_slice(format!("/F2 {} Tf\n", FONT_SIZE).as_bytes());
+                content.extend(text_operator(&format!(
+                    "{}{}",
+                    MONOCHROME_MARKER, line.text
+                )));
+                content.extend_from_slice(format!("/F1 {} Tf\n", FONT_SIZE).as_bytes());
+            }
+            Some([r, g, b]) => {
+                let [r, g, b] = [r, g, b].map(|channel| channel as f32 / 255.0);
+                content.extend_from_slice(format!("{:.3} {:.3} {:.3} rg\n", r, g, b).as_bytes());
+                content.extend(text_operator(&line.text));
+                content.extend_from_slice(b"0 g\n");
+            }
+        }
+    }
+    content.extend_from_slice(b"ET\n");
+    content
+}
+
+/// Quebra o texto em linhas de até `width` caracteres, entre as palavras; uma
+/// palavra maior que a linha fica inteira. As continuações mantêm o recuo do início
+/// do texto.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    if text.chars().count() <= width {
+        return vec![text.to_string()];
+    }
+    let indent = &text[..text.len() - text.trim_start().len()];
+    let mut lines = Vec::new();
+    let mut current = indent.to_string();
+    for word in text.split_whitespace() {
+        let length = current.chars().count();
+        if length > indent.len() && length + 1 + word.chars().count() > width {
+            lines.push(std::mem::replace(&mut current, indent.to_string()));
+        }
+        if current.len() > indent.len() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    lines.push(current);
+    lines
+}
+
+/// Operador que escreve uma linha e avança para a próxima: `(texto) '`.
+fn text_operator(text: &str) -> Vec<u8> {
+    let mut operator = vec![b'('];
+    operator.extend(encode_text(text));
+    operator.extend_from_slice(b") '\n");
+    operator
+}
+
+/// Converte o texto para WinAnsi, escapando os caracteres especiais das strings do
+/// PDF. Caracteres fora da codificação (como emojis) viram '?'.
+fn encode_text(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => bytes.extend_from_slice(&[b'\\', c as u8]),
+            '\t' => bytes.push(b' '),
+            '–' => bytes.push(0x96),
+            '—' => bytes.push(0x97),
+            '−' => bytes.push(b'-'),
+            ' '..='~' | '\u{a0}'..='\u{ff}' => bytes.push(c as u32 as u8),
+            _ => bytes.push(b'?'),
+        }
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn document_is_well_formed() {
+        let pdf = document(
+            "Título",
+            &["Idade Corrigida: 11 semanas".to_string().into()],
+            false,
+        );
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+
+        // Cada entrada da tabela 'xref' aponta para o início do objeto correspondente.
+        let text = String::from_utf8_lossy(&pdf);
+        let xref = text.rfind("xref\n").unwrap();
+        let entries = text[xref..].lines().skip(3).take(6);
+        for (index, entry) in entries.enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            let header = format!("{} 0 obj", index + 1);
+            assert_eq!(&pdf[offset..offset + header.len()], header.as_bytes());
+        }
+    }
+
+    #[test]
+    fn lines_beyond_a_page_continue_on_the_next() {
+        let lines: Vec<Line> = (1..=LINES_PER_PAGE * 2 + 1)
+            .map(|number| format!("Linha {}", number).into())
+            .collect();
+        let pdf = document("Título", &lines, false);
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("/Kids [5 0 R 7 0 R 9 0 R] /Count 3"));
+        assert_eq!(text.matches("/Type /Page ").count(), 3);
+        let title = b"(T\xedtulo) '";
+        assert_eq!(pdf.windows(title.len()).filter(|w| w == title).count(), 3);
+        for number in 1..=lines.len() {
+            assert!(
+                text.contains(&format!("(Linha {}) '", number)),
+                "{}",
+                number
+            );
+        }
+    }
+
+    #[test]
+    fn long_lines_are_wrapped_between_words() {
+        assert_eq!(wrap("Idade: 11 semanas", 40), ["Idade: 11 semanas"]);
+        assert_eq!(
+            wrap(
+                "    (idade corrigida = idade cronológica − prematuridade)",
+                30
+            ),
+            [
+                "    (idade corrigida = idade",
+                "    cronológica −",
+                "    prematuridade)"
+            ]
+        );
+        assert_eq!(wrap("abcdefghij klm", 5), ["abcdefghij", "klm"]);
+    }
+
+    #[test]
+    fn monochrome_highlights_use_bold_and_a_marker_instead_of_color() {
+        let lines = [Line {
+            text: "Classificação: Muito prematuro".to_string(),
+            highlight: Some([190, 80, 0]),
+        }];
+        let contains = |pdf: &[u8], needle: &[u8]| pdf.windows(needle.len()).any(|w| w == needle);
+
+        let color = document("Título", &lines, false);
+        assert!(contains(&color, b"0.745 0.314 0.000 rg\n(Classifica"));
+
+        let monochrome = document("Título", &lines, true);
+        assert!(!contains(&monochrome, b" rg\n"));
+        assert!(contains(&monochrome, b"/F2 10 Tf\n(\xbb Classifica"));
+    }
+
+    #[test]
+    fn text_is_encoded_as_win_ansi_and_escaped() {
+        assert_eq!(encode_text("Pós (DPP)"), b"P\xf3s \\(DPP\\)".to_vec());
+        assert_eq!(encode_text("31–33 👶"), b"31\x9633 ?".to_vec());
+    }
+}
@@ -0,0 +1,82 @@
+// Cadastro de pacientes acompanhados, salvo entre sessões e independente do
+// cálculo atual.
+use crate::storage;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::hash::{BuildHasher, RandomState};
+use std::io;
+use std::path::Path;
+
+/// Nome do arquivo do cadastro no diretório de configuração.
+const ROSTER_FILE: &str = "pacientes.json";
+
+/// Paciente salvo no cadastro.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Patient {
+    pub name: String,
+    pub birthdate: NaiveDate,
+    pub gestational_weeks: i32,
+    pub gestational_days: i32,
+    /// Observador ou fonte da idade gestacional. Ausente nos cadastros antigos.
+    #[serde(default)]
+    pub observer: Option<String>,
+}
+
+/// Paciente exportado sem identificação, para pesquisa: apenas a idade
+/// gestacional e as idades derivadas da data de nascimento, em dias.
+#[derive(Serialize)]
+pub struct DeidentifiedRecord {
+    pub id: String,
+    pub gestational_weeks: i32,
+    pub gestational_days: i32,
+    pub chronological_age_days: i64,
+    pub corrected_age_days: i64,
+    pub post_menstrual_age_days: i64,
+}
+
+impl Patient {
+    /// Identificador anônimo do paciente, estável durante a sessão para a mesma
+    /// `session_key` e impossível de relacionar ao nome sem ela. A chave é sorteada
+    /// a cada execução, então exportações de sessões diferentes não se cruzam.
+    pub fn anonymous_id(&self, session_key: &RandomState) -> String {
+        let hash = session_key.hash_one((&self.name, self.birthdate));
+        format!("P{:016X}", hash)
+    }
+}
+
+/// Lista de pacientes salvos.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Roster {
+    pub patients: Vec<Patient>,
+}
+
+impl Roster {
+    /// Carrega o cadastro salvo. Um arquivo ausente ou corrompido resulta em um
+    /// cadastro vazio.
+    pub fn load() -> Self {
+        storage::load(ROSTER_FILE)
+    }
+
+    /// Grava o cadastro no diretório de configuração.
+    pub fn save(&self) -> io::Result<()> {
+        storage::save(ROSTER_FILE, self)
+    }
+
+    /// Adiciona os pacientes de um arquivo exportado ao cadastro atual.
+    pub fn import(&mut self, path: &Path) -> io::Result<usize> {
+        let imported: Roster = storage::read_json(path)?;
+        let count = imported.patients.len();
+        self.patients.extend(imported.patients);
+        Ok(count)
+    }
+
+    /// Exporta o cadastro completo para um arquivo JSON.
+    pub fn export(&self, path: &Path) -> io::Result<()> {
+        storage::write_json(path, self)
+    }
+
+    /// Exporta registros sem identificação para um arquivo JSON.
+    pub fn export_deidentified(path: &Path, records: &[DeidentifiedRecord]) -> io::Result<()> {
+        storage::write_json(path, &records)
+    }
+}
@@ -0,0 +1,480 @@
+// Preferências do usuário, salvas entre sessões no diretório de configuração.
+use crate::age::{AVERAGE_MONTH_DAYS, FULL_TERM_WEEKS};
+use crate::i18n::Lang;
+use crate::storage;
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+
+/// Nome do arquivo de configurações no diretório de configuração.
+const SETTINGS_FILE: &str = "configuracoes.json";
+
+/// Convenção cultural usada para exibir a idade em anos. Afeta apenas a exibição
+/// dos anos; semanas, meses e dias são sempre calculados da forma padrão.
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum AgeConvention {
+    /// Idade internacional: anos completos desde a data de referência, começando
+    /// em 0 no nascimento e aumentando a cada aniversário.
+    #[default]
+    International,
+    /// Contagem coreana tradicional: o bebê tem 1 ano ao nascer e ganha mais um
+    /// ano a cada 1º de janeiro, independentemente da data do aniversário.
+    Korean,
+}
+
+impl AgeConvention {
+    /// Nome da convenção exibido na interface.
+    pub fn label(self) -> &'static str {
+        match self {
+            AgeConvention::International => "Internacional",
+            AgeConvention::Korean => "Contagem coreana",
+        }
+    }
+
+    /// Idade em anos segundo a convenção, contada a partir de `start` até `today`.
+    /// `completed_years` é a idade internacional já calculada.
+    pub fn years(self, completed_years: i32, start: NaiveDate, today: NaiveDate) -> i32 {
+        match self {
+            AgeConvention::International => completed_years,
+            AgeConvention::Korean => today.year() - start.year() + 1,
+        }
+    }
+}
+
+/// Forma de contar os meses totais de idade, como em "12 semanas (2 meses)". A
+/// contagem padrão é a de calendário, que coincide com a idade em anos, meses e
+/// dias; a média fica como opção para quem segue essa convenção.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum MonthCount {
+    /// Dias de idade divididos pela duração média do mês (30,4375 dias).
+    AverageDays,
+    /// Meses de calendário completos, os mesmos da idade em anos, meses e dias.
+    #[default]
+    Calendar,
+}
+
+impl MonthCount {
+    /// Nome da forma de contagem exibido na interface.
+    pub fn label(self) -> &'static str {
+        match self {
+            MonthCount::AverageDays => "Média de 30,4375 dias por mês",
+            MonthCount::Calendar => "Meses de calendário completos",
+        }
+    }
+
+    /// Meses totais de uma idade de `years` anos, `months` meses e `total_days` dias
+    /// no total.
+    pub fn total_months(self, years: i32, months: i32, total_days: i64) -> i64 {
+        match self {
+            MonthCount::AverageDays => (total_days as f64 / AVERAGE_MONTH_DAYS).floor() as i64,
+            MonthCount::Calendar => (years * 12 + months) as i64,
+        }
+    }
+}
+
+/// Idade gestacional usada como termo na idade corrigida. O padrão são 40 semanas,
+/// a mesma referência da data provável do parto; alguns protocolos corrigem para
+/// 37 ou 38 semanas.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum CorrectionTerm {
+    Weeks37,
+    Weeks38,
+    #[default]
+    Weeks40,
+}
+
+impl CorrectionTerm {
+    /// Semanas completas de gestação do termo.
+    pub fn weeks(self) -> i32 {
+        match self {
+            CorrectionTerm::Weeks37 => 37,
+            CorrectionTerm::Weeks38 => 38,
+            CorrectionTerm::Weeks40 => FULL_TERM_WEEKS,
+        }
+    }
+
+    /// Nome da opção exibido na interface.
+    pub fn label(self) -> &'static str {
+        match self {
+            CorrectionTerm::Weeks37 => "37 semanas",
+            CorrectionTerm::Weeks38 => "38 semanas",
+            CorrectionTerm::Weeks40 => "40 semanas (data provável do parto)",
+        }
+    }
+}
+
+/// De onde vem a idade gestacional ao nascer usada no cálculo.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GestationalAgeSource {
+    /// Semanas e dias digitados no formulário.
+    Informed,
+    /// Calculada a partir da data da última menstruação (DUM).
+    Lmp,
+}
+
+impl GestationalAgeSource {
+    /// Nome da fonte exibido no resultado.
+    pub fn label(self) -> &'static str {
+        match self {
+            GestationalAgeSource::Informed => "semanas e dias informados",
+            GestationalAgeSource::Lmp => "DUM",
+        }
+    }
+}
+
+/// Ordem de prioridade das fontes da idade gestacional. O cálculo usa a primeira
+/// fonte válida na ordem; o padrão mantém as semanas e dias informados à frente da
+/// DUM, que serve então apenas para conferi-los.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum GestationalAgePriority {
+    #[default]
+    InformedFirst,
+    LmpFirst,
+}
+
+impl GestationalAgePriority {
+    /// As fontes, da mais para a menos prioritária.
+    pub fn order(self) -> [GestationalAgeSource; 2] {
+        match self {
+            GestationalAgePriority::InformedFirst => {
+                [GestationalAgeSource::Informed, GestationalAgeSource::Lmp]
+            }
+            GestationalAgePriority::LmpFirst => {
+                [GestationalAgeSource::Lmp, GestationalAgeSource::Informed]
+            }
+        }
+    }
+
+    /// Nome da opção exibido na interface.
+    pub fn label(self) -> &'static str {
+        match self {
+            GestationalAgePriority::InformedFirst => "Semanas e dias informados, depois a DUM",
+            GestationalAgePriority::LmpFirst => "DUM, depois as semanas e dias informados",
+        }
+    }
+}
+
+/// Modo de exibição do resultado.
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum DisplayMode {
+    /// Resultado completo e técnico, para profissionais de saúde.
+    #[default]
+    Clinician,
+    /// Resultado em frases simples, para mostrar às famílias.
+    Parent,
+    /// Idades desde o nascimento, desde o termo e pós-menstrual lado a lado.
+    Complete,
+}
+
+impl DisplayMode {
+    /// Nome do modo exibido na interface.
+    pub fn label(self) -> &'static str {
+        match self {
+            DisplayMode::Clinician => "Modo clínico",
+            DisplayMode::Parent => "Modo para os pais",
+            DisplayMode::Complete => "Modo completo",
+        }
+    }
+
+    /// O próximo modo, para alternar entre eles com um atalho.
+    pub fn next(self) -> Self {
+        match self {
+            DisplayMode::Clinician => DisplayMode::Parent,
+            DisplayMode::Parent => DisplayMode::Complete,
+            DisplayMode::Complete => DisplayMode::Clinician,
+        }
+    }
+}
+
+/// Tema de cores da interface.
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum Theme {
+    /// Segue o tema do sistema operacional, quando ele pode ser detectado.
+    #[default]
+    System,
+    Light,
+    /// Mais confortável em ambientes com pouca luz, como a UTI neonatal.
+    Dark,
+}
+
+impl Theme {
+    /// Nome do tema exibido na interface.
+    pub fn label(self) -> &'static str {
+        match self {
+            Theme::System => "Seguir o sistema",
+            Theme::Light => "Claro",
+            Theme::Dark => "Escuro",
+        }
+    }
+}
+
+/// Efeito da tecla Enter nos campos do formulário.
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum EnterBehavior {
+    /// Avança para o próximo campo; o último campo calcula.
+    #[default]
+    NextField,
+    /// Calcula de qualquer campo, se todos estiverem válidos.
+    Calculate,
+}
+
+impl EnterBehavior {
+    /// Nome da opção exibido na interface.
+    pub fn label(self) -> &'static str {
+        match self {
+            EnterBehavior::NextField => "Avança para o próximo campo",
+            EnterBehavior::Calculate => "Calcula, se os dados estiverem completos",
+        }
+    }
+}
+
+/// Dias da semana em que o ambulatório atende, indexados a partir da segunda-feira.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ClinicDays(pub [bool; 7]);
+
+impl Default for ClinicDays {
+    /// De segunda a sexta-feira.
+    fn default() -> Self {
+        ClinicDays([true, true, true, true, true, false, false])
+    }
+}
+
+impl ClinicDays {
+    /// Nomes abreviados dos dias, na mesma ordem do vetor.
+    pub const LABELS: [&'static str; 7] = ["Seg", "Ter", "Qua", "Qui", "Sex", "Sáb", "Dom"];
+
+    /// Indica se há atendimento no dia da semana da data.
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.0[date.weekday().num_days_from_monday() as usize]
+    }
+
+    /// Move a data para o dia de atendimento mais próximo. Em caso de empate, usa o
+    /// dia seguinte, para não antecipar a consulta. Sem nenhum dia marcado, a data
+    /// fica como está.
+    pub fn snap(&self, date: NaiveDate) -> NaiveDate {
+        (0..7)
+            .flat_map(|offset| [date + Duration::days(offset), date - Duration::days(offset)])
+            .find(|candidate| self.contains(*candidate))
+            .unwrap_or(date)
+    }
+}
+
+/// Escala da interface (tamanho do texto e dos controles), para quem precisa de
+/// letras maiores.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct UiScale(pub f32);
+
+impl Default for UiScale {
+    /// Tamanho normal, sem ampliação.
+    fn default() -> Self {
+        UiScale(1.0)
+    }
+}
+
+impl UiScale {
+    pub const MIN: f32 = 0.8;
+    pub const MAX: f32 = 2.0;
+    /// Quanto cada clique nos botões "−" e "+" altera a escala.
+    pub const STEP: f32 = 0.1;
+
+    /// A escala dentro dos limites, mesmo que o arquivo tenha sido editado à mão.
+    pub fn value(self) -> f32 {
+        if self.0.is_finite() {
+            self.0.clamp(Self::MIN, Self::MAX)
+        } else {
+            1.0
+        }
+    }
+
+    /// A escala `steps` passos acima (ou abaixo, se negativo), arredondada para o
+    /// passo mais próximo.
+    pub fn step(self, steps: i32) -> Self {
+        let scaled = self.value() + steps as f32 * Self::STEP;
+        UiScale(((scaled / Self::STEP).round() * Self::STEP).clamp(Self::MIN, Self::MAX))
+    }
+}
+
+/// Preferências do usuário. Campos ausentes no arquivo assumem o valor padrão.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Idioma da interface e dos textos gerados.
+    pub language: Lang,
+    pub age_convention: AgeConvention,
+    /// Como são contados os meses totais exibidos junto das semanas.
+    pub month_count: MonthCount,
+    /// Idade gestacional para a qual a prematuridade é corrigida.
+    pub correction_term: CorrectionTerm,
+    /// Mostra o termo de referência junto aos campos de idade gestacional também
+    /// quando é o padrão de 40 semanas (os outros termos são sempre indicados).
+    pub show_default_term: bool,
+    /// Qual fonte da idade gestacional prevalece quando a DUM também é informada.
+    pub gestational_age_priority: GestationalAgePriority,
+    pub display_mode: DisplayMode,
+    pub theme: Theme,
+    /// Escala da interface, ajustada em "Exibição > Tamanho do texto".
+    pub ui_scale: UiScale,
+    /// Se a seção "Detalhes do cálculo" fica expandida.
+    pub details_expanded: bool,
+    /// Mostra junto a cada resultado a fórmula usada (modo didático).
+    pub show_formulas: bool,
+    /// No modo para os pais, expressa os dias como frações da semana quando o
+    /// arredondamento é natural ("8 semanas e meia").
+    pub parent_week_fractions: bool,
+    pub enter_behavior: EnterBehavior,
+    /// Ao entrar em um campo, deixa o cursor no fim em vez de selecionar o texto.
+    pub cursor_at_end_on_focus: bool,
+    /// Aceita a idade gestacional em semanas decimais ("32,5") no campo de semanas.
+    pub decimal_gestational_weeks: bool,
+    /// Destaca a área de resultados por um instante após cada cálculo.
+    pub flash_on_calculate: bool,
+    /// Toca um som curto após cada cálculo (apenas com a feature 'sound').
+    pub sound_on_calculate: bool,
+    /// Com a janela minimizada, mostra no título a idade corrigida do último cálculo.
+    pub age_in_taskbar: bool,
+    /// Mostra a idade corrigida de hoje em um ícone na bandeja do sistema (apenas
+    /// com a feature 'tray', no Windows e no macOS).
+    pub tray_icon: bool,
+    /// Exporta o PDF em preto e branco, com os destaques em negrito em vez de cor.
+    pub monochrome_export: bool,
+    /// Mostra o volume hídrico de referência do primeiro mês, pelo peso informado.
+    pub feeding_reference: bool,
+    /// Limpa o formulário após cada exportação bem-sucedida do resultado.
+    pub clear_after_export: bool,
+    /// Ajusta as datas das consultas de seguimento para os dias de atendimento.
+    pub snap_to_clinic_days: bool,
+    pub clinic_days: ClinicDays,
+}
+
+impl Settings {
+    /// Carrega as configurações salvas, ou as padrão se não houver arquivo válido.
+    pub fn load() -> Self {
+        let value: serde_json::Value = storage::load(SETTINGS_FILE);
+        Self::from_json(value).0
+    }
+
+    /// Importa as configurações de um arquivo exportado em outra máquina. Retorna
+    /// também os nomes dos campos inválidos, que foram substituídos pelo padrão.
+    pub fn import(path: &Path) -> io::Result<(Self, Vec<String>)> {
+        let value: serde_json::Value = storage::read_json(path)?;
+        if !value.is_object() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "o arquivo não contém configurações",
+            ));
+        }
+        Ok(Self::from_json(value))
+    }
+
+    /// Exporta as configurações para um arquivo JSON.
+    pub fn export(&self, path: &Path) -> io::Result<()> {
+        storage::write_json(path, self)
+    }
+
+    /// Monta as configurações a partir de um JSON, campo a campo: cada campo
+    /// inválido ou desconhecido é ignorado e o valor padrão é mantido, em vez de
+    /// descartar o arquivo inteiro.
+    fn from_json(value: serde_json::Value) -> (Self, Vec<String>) {
+        let mut merged = serde_json::to_value(Self::default()).unwrap_or_default();
+        let mut rejected = Vec::new();
+        if let (Some(fields), Some(target)) = (value.as_object(), merged.as_object_mut()) {
+            for (key, field) in fields {
+                let Some(previous) = target.get(key).cloned() else {
+                    rejected.push(key.clone());
+                    continue;
+                };
+                target.insert(key.clone(), field.clone());
+                if serde_json::from_value::<Self>(serde_json::Value::Object(target.clone()))
+                    .is_err()
+                {
+                    target.insert(key.clone(), previous);
+                    rejected.push(key.clone());
+                }
+            }
+        }
+        let settings = serde_json::from_value(merged).unwrap_or_default();
+        (settings, rejected)
+    }
+
+    /// Grava as configurações no diretório de configuração.
+    pub fn save(&self) -> io::Result<()> {
+        storage::save(SETTINGS_FILE, self)
+    }
+
+    /// Data em que uma consulta calculada para `date` deve ser marcada.
+    pub fn appointment_date(&self, date: NaiveDate) -> NaiveDate {
+        if self.snap_to_clinic_days {
+            self.clinic_days.snap(date)
+        } else {
+            date
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dates::date;
+
+    #[test]
+    fn ui_scale_steps_stay_within_the_limits() {
+        assert_eq!(UiScale::default().step(1).0, 1.1);
+        assert_eq!(UiScale(1.95).step(1).0, UiScale::MAX);
+        assert_eq!(UiScale(0.8).step(-1).0, UiScale::MIN);
+        assert_eq!(UiScale(5.0).value(), UiScale::MAX);
+        assert_eq!(UiScale(f32::NAN).value(), 1.0);
+    }
+
+    #[test]
+    fn month_counts_differ_for_a_baby_born_on_the_31st() {
+        // De 31/01 a 30/04/2024: 3 meses de calendário (o de fevereiro se completa
+        // em 29/02), mas só 90 dias, menos de 3 meses de 30,4375 dias.
+        let total_days = date(30, 4, 2024)
+            .signed_duration_since(date(31, 1, 2024))
+            .num_days();
+        assert_eq!(total_days, 90);
+        assert_eq!(MonthCount::Calendar.total_months(0, 3, total_days), 3);
+        assert_eq!(MonthCount::AverageDays.total_months(0, 3, total_days), 2);
+    }
+
+    #[test]
+    fn weekdays_are_kept() {
+        // 15/10/2026 é uma quinta-feira.
+        assert_eq!(
+            ClinicDays::default().snap(date(15, 10, 2026)),
+            date(15, 10, 2026)
+        );
+    }
+
+    #[test]
+    fn saturday_moves_back_to_friday() {
+        assert_eq!(
+            ClinicDays::default().snap(date(17, 10, 2026)),
+            date(16, 10, 2026)
+        );
+    }
+
+    #[test]
+    fn sunday_moves_forward_to_monday() {
+        assert_eq!(
+            ClinicDays::default().snap(date(18, 10, 2026)),
+            date(19, 10, 2026)
+        );
+    }
+
+    #[test]
+    fn ties_move_forward() {
+        // Atendimento só às segundas e sextas: a quarta está a dois dias de cada uma.
+        let clinic_days = ClinicDays([true, false, false, false, true, false, false]);
+        assert_eq!(clinic_days.snap(date(14, 10, 2026)), date(16, 10, 2026));
+    }
+
+    #[test]
+    fn no_clinic_days_keeps_the_date() {
+        assert_eq!(
+            ClinicDays([false; 7]).snap(date(17, 10, 2026)),
+            date(17, 10, 2026)
+        );
+    }
+}
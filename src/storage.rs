@@ -0,0 +1,48 @@
+// Persistência dos dados da aplicação em arquivos JSON no diretório de
+// configuração do usuário (por exemplo, '~/.config/calculadora_gestacional' no Linux
+// ou '%APPDATA%\calculadora_gestacional' no Windows).
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Retorna o diretório onde os arquivos da aplicação são gravados, se existir
+/// um diretório de configuração para o usuário atual.
+fn config_dir() -> Option<PathBuf> {
+    directories::ProjectDirs::from("br", "paulomunhoz", "calculadora_gestacional")
+        .map(|dirs| dirs.config_dir().to_path_buf())
+}
+
+/// Lê um valor de um arquivo JSON qualquer.
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> io::Result<T> {
+    let contents = fs::read_to_string(path)?;
+    serde_json::from_str(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Grava um valor em um arquivo JSON qualquer, formatado para leitura humana.
+pub fn write_json<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    let contents = serde_json::to_string_pretty(value)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    fs::write(path, contents)
+}
+
+/// Carrega um arquivo do diretório de configuração. Se o arquivo não existir ou
+/// estiver corrompido, retorna o valor padrão.
+pub fn load<T: DeserializeOwned + Default>(file_name: &str) -> T {
+    config_dir()
+        .and_then(|dir| read_json(&dir.join(file_name)).ok())
+        .unwrap_or_default()
+}
+
+/// Grava um arquivo no diretório de configuração, criando o diretório se necessário.
+pub fn save<T: Serialize>(file_name: &str, value: &T) -> io::Result<()> {
+    let dir = config_dir().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "diretório de configuração não encontrado",
+        )
+    })?;
+    fs::create_dir_all(&dir)?;
+    write_json(&dir.join(file_name), value)
+}
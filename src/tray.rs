@@ -0,0 +1,80 @@
+// Ícone na bandeja do sistema com a idade corrigida de hoje do último cálculo,
+// exibida ao passar o mouse, para quem deixa a calculadora minimizada.
+//
+// O ícone só existe com a feature 'tray' ('cargo build --release --features tray')
+// e no Windows e no macOS, onde a bandeja funciona com o laço de eventos da própria
+// janela. No Linux, a 'tray-icon' exige um laço do GTK em outra thread e as
+// bibliotecas de desenvolvimento do GTK; ali, como nas compilações sem a feature,
+// `Tray::new` retorna `None`, a opção fica desativada e resta a idade no título da
+// janela minimizada.
+
+/// Se esta compilação mostra o ícone na bandeja.
+pub const SUPPORTED: bool = cfg!(all(feature = "tray", any(windows, target_os = "macos")));
+
+#[cfg(all(feature = "tray", any(windows, target_os = "macos")))]
+pub use native::Tray;
+
+#[cfg(not(all(feature = "tray", any(windows, target_os = "macos"))))]
+pub use unsupported::Tray;
+
+#[cfg(all(feature = "tray", any(windows, target_os = "macos")))]
+mod native {
+    use tray_icon::{Icon, TrayIcon, TrayIconBuilder};
+
+    /// Lado do ícone desenhado, em pixels.
+    const ICON_SIZE: u32 = 32;
+
+    /// Ícone na bandeja; é removido quando o valor é descartado.
+    pub struct Tray {
+        icon: TrayIcon,
+    }
+
+    impl Tray {
+        /// Cria o ícone com o texto exibido ao passar o mouse. Deve ser chamado na
+        /// thread da janela, com o laço de eventos já em execução.
+        pub fn new(tooltip: &str) -> Option<Self> {
+            let icon = TrayIconBuilder::new()
+                .with_icon(circle_icon()?)
+                .with_tooltip(tooltip)
+                .build()
+                .map_err(|error| log::debug!("Ícone da bandeja indisponível: {}", error))
+                .ok()?;
+            Some(Self { icon })
+        }
+
+        /// Troca o texto exibido ao passar o mouse.
+        pub fn set_tooltip(&self, tooltip: &str) {
+            let _ = self.icon.set_tooltip(Some(tooltip));
+        }
+    }
+
+    /// Círculo azul sobre fundo transparente, desenhado aqui para não depender de
+    /// decodificar o 'Product.ico'.
+    fn circle_icon() -> Option<Icon> {
+        let center = (ICON_SIZE as f32 - 1.0) / 2.0;
+        let radius = ICON_SIZE as f32 / 2.0 - 1.0;
+        let mut rgba = Vec::with_capacity((ICON_SIZE * ICON_SIZE * 4) as usize);
+        for y in 0..ICON_SIZE {
+            for x in 0..ICON_SIZE {
+                let distance = (x as f32 - center).hypot(y as f32 - center);
+                let alpha = if distance <= radius { 255 } else { 0 };
+                rgba.extend_from_slice(&[40, 110, 200, alpha]);
+            }
+        }
+        Icon::from_rgba(rgba, ICON_SIZE, ICON_SIZE).ok()
+    }
+}
+
+#[cfg(not(all(feature = "tray", any(windows, target_os = "macos"))))]
+mod unsupported {
+    /// Sem suporte à bandeja nesta compilação: o ícone nunca é criado.
+    pub struct Tray;
+
+    impl Tray {
+        pub fn new(_tooltip: &str) -> Option<Self> {
+            None
+        }
+
+        pub fn set_tooltip(&self, _tooltip: &str) {}
+    }
+}
@@ -0,0 +1,149 @@
+// Pilha das ações destrutivas da sessão (limpar o formulário, excluir pacientes),
+// que podem ser desfeitas em ordem inversa com "Desfazer" ou Ctrl+Z.
+use crate::i18n::{t, tf, Lang};
+use crate::roster::Patient;
+
+/// Quantas ações ficam guardadas; ao passar disso, as mais antigas são esquecidas.
+pub const MAX_UNDO_STEPS: usize = 20;
+
+/// Conteúdo dos campos do formulário, guardado ao limpar para poder desfazer.
+pub struct FormSnapshot {
+    pub birth_date: String,
+    pub gestational_weeks: String,
+    pub gestational_days: String,
+    pub lmp: String,
+    pub observer: String,
+    /// Peso atual, em gramas.
+    pub weight: String,
+    pub reference_date: String,
+    /// Datas de nascimento dos outros bebês de uma gestação múltipla.
+    pub sibling_birth_dates: [String; 2],
+}
+
+/// Estado anterior a uma ação destrutiva, suficiente para revertê-la.
+pub enum UndoEntry {
+    /// Formulário limpo pelo botão "Limpar" ou após uma exportação.
+    ClearedForm(FormSnapshot),
+    /// Paciente excluído do cadastro, com a posição que ocupava na lista.
+    DeletedPatient { index: usize, patient: Patient },
+}
+
+impl UndoEntry {
+    /// Descrição da ação, para a dica do botão "Desfazer".
+    pub fn description(&self, lang: Lang) -> String {
+        match self {
+            UndoEntry::ClearedForm(_) => t("Desfazer a limpeza do formulário", lang).to_string(),
+            UndoEntry::DeletedPatient { patient, .. } => {
+                tf("Desfazer a exclusão de {}", lang, &[&patient.name])
+            }
+        }
+    }
+}
+
+/// Pilha de ações a desfazer, limitada a `MAX_UNDO_STEPS`.
+#[derive(Default)]
+pub struct UndoStack {
+    entries: Vec<UndoEntry>,
+}
+
+impl UndoStack {
+    /// Guarda uma ação, descartando a mais antiga se a pilha estiver cheia.
+    pub fn push(&mut self, entry: UndoEntry) {
+        if self.entries.len() == MAX_UNDO_STEPS {
+            self.entries.remove(0);
+        }
+        self.entries.push(entry);
+    }
+
+    /// Retira a ação mais recente.
+    pub fn pop(&mut self) -> Option<UndoEntry> {
+        self.entries.pop()
+    }
+
+    /// A ação mais recente, sem retirá-la.
+    pub fn last(&self) -> Option<&UndoEntry> {
+        self.entries.last()
+    }
+}
+
+/// Devolve um paciente excluído à posição que ocupava, ou ao fim da lista se ela
+/// tiver encolhido desde então. Retorna a posição em que ele foi inserido.
+pub fn restore_patient(patients: &mut Vec<Patient>, index: usize, patient: Patient) -> usize {
+    let index = index.min(patients.len());
+    patients.insert(index, patient);
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn patient(name: &str) -> Patient {
+        Patient {
+            name: name.to_string(),
+            birthdate: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            gestational_weeks: 32,
+            gestational_days: 0,
+            observer: None,
+        }
+    }
+
+    fn deleted(name: &str) -> UndoEntry {
+        UndoEntry::DeletedPatient {
+            index: 0,
+            patient: patient(name),
+        }
+    }
+
+    fn name(entry: Option<UndoEntry>) -> String {
+        match entry {
+            Some(UndoEntry::DeletedPatient { patient, .. }) => patient.name,
+            Some(UndoEntry::ClearedForm(snapshot)) => snapshot.birth_date,
+            None => String::new(),
+        }
+    }
+
+    #[test]
+    fn most_recent_action_is_undone_first() {
+        let mut stack = UndoStack::default();
+        stack.push(deleted("Ana"));
+        stack.push(UndoEntry::ClearedForm(FormSnapshot {
+            birth_date: "01/01/2024".to_string(),
+            gestational_weeks: "32".to_string(),
+            gestational_days: "4".to_string(),
+            lmp: String::new(),
+            observer: String::new(),
+            weight: String::new(),
+            reference_date: String::new(),
+            sibling_birth_dates: Default::default(),
+        }));
+        assert_eq!(name(stack.pop()), "01/01/2024");
+        assert_eq!(name(stack.pop()), "Ana");
+        assert!(stack.pop().is_none());
+    }
+
+    #[test]
+    fn oldest_action_is_dropped_when_full() {
+        let mut stack = UndoStack::default();
+        for i in 0..=MAX_UNDO_STEPS {
+            stack.push(deleted(&i.to_string()));
+        }
+        let mut names = Vec::new();
+        while let Some(entry) = stack.pop() {
+            names.push(name(Some(entry)));
+        }
+        assert_eq!(names.len(), MAX_UNDO_STEPS);
+        assert_eq!(names.last().map(String::as_str), Some("1"));
+    }
+
+    #[test]
+    fn deleted_patient_returns_to_its_position() {
+        let mut patients = vec![patient("Ana"), patient("Caio")];
+        assert_eq!(restore_patient(&mut patients, 1, patient("Bia")), 1);
+        let names: Vec<&str> = patients.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["Ana", "Bia", "Caio"]);
+        // Se a lista encolheu, o paciente volta para o fim.
+        assert_eq!(restore_patient(&mut patients, 10, patient("Davi")), 3);
+    }
+}
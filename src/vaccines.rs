@@ -0,0 +1,103 @@
+// Calendário de vacinação da criança do Programa Nacional de Imunizações (PNI) até
+// os 15 meses. As vacinas são aplicadas pela idade cronológica, também nos
+// prematuros, e não pela idade corrigida. Os textos ficam todos nesta tabela para
+// facilitar a atualização quando o calendário mudar, e a tradução.
+use chrono::{Months, NaiveDate};
+
+/// Vacinas previstas para uma idade cronológica.
+pub struct VaccineMilestone {
+    pub months: u32,
+    pub vaccines: &'static str,
+    /// Observação sobre prazos ou condições de aplicação, quando houver.
+    pub note: Option<&'static str>,
+}
+
+/// Vacinas do calendário básico da criança, por idade cronológica em meses.
+const SCHEDULE: &[VaccineMilestone] = &[
+    VaccineMilestone {
+        months: 0,
+        vaccines: "BCG e Hepatite B",
+        note: Some("A BCG é aplicada quando o bebê atinge 2 kg."),
+    },
+    VaccineMilestone {
+        months: 2,
+        vaccines: "Pentavalente, VIP (poliomielite), Pneumocócica 10-valente e Rotavírus (1ª dose)",
+        note: Some("Rotavírus: 1ª dose até 3 meses e 15 dias de idade cronológica."),
+    },
+    VaccineMilestone {
+        months: 3,
+        vaccines: "Meningocócica C (1ª dose)",
+        note: None,
+    },
+    VaccineMilestone {
+        months: 4,
+        vaccines: "Pentavalente, VIP (poliomielite), Pneumocócica 10-valente e Rotavírus (2ª dose)",
+        note: Some("Rotavírus: 2ª dose até 7 meses e 29 dias de idade cronológica."),
+    },
+    VaccineMilestone {
+        months: 5,
+        vaccines: "Meningocócica C (2ª dose)",
+        note: None,
+    },
+    VaccineMilestone {
+        months: 6,
+        vaccines: "Pentavalente e VIP (3ª dose), Influenza e Covid-19",
+        note: None,
+    },
+    VaccineMilestone {
+        months: 9,
+        vaccines: "Febre amarela",
+        note: None,
+    },
+    VaccineMilestone {
+        months: 12,
+        vaccines: "Tríplice viral (1ª dose), Pneumocócica 10-valente e Meningocócica (reforço)",
+        note: None,
+    },
+    VaccineMilestone {
+        months: 15,
+        vaccines: "DTP e VIP (1º reforço), Hepatite A e Tetraviral",
+        note: None,
+    },
+];
+
+/// Vacinas ainda previstas a partir de `today`, com a data em que cada grupo é
+/// devido, contada pela idade cronológica desde `birthdate`.
+pub fn upcoming(
+    birthdate: NaiveDate,
+    today: NaiveDate,
+) -> Vec<(NaiveDate, &'static VaccineMilestone)> {
+    SCHEDULE
+        .iter()
+        .filter_map(|milestone| {
+            let due = birthdate.checked_add_months(Months::new(milestone.months))?;
+            (due >= today).then_some((due, milestone))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dates::date;
+
+    #[test]
+    fn only_vaccines_still_due_are_listed() {
+        let upcoming = upcoming(date(1, 1, 2024), date(15, 5, 2024));
+        let months: Vec<u32> = upcoming
+            .iter()
+            .map(|(_, milestone)| milestone.months)
+            .collect();
+        assert_eq!(months, [5, 6, 9, 12, 15]);
+        assert_eq!(upcoming[0].0, date(1, 6, 2024));
+    }
+
+    #[test]
+    fn due_dates_follow_the_chronological_age() {
+        // Nascido em 31/01: os 2 meses se completam no fim de março.
+        let upcoming = upcoming(date(31, 1, 2024), date(31, 1, 2024));
+        assert_eq!(upcoming[0].0, date(31, 1, 2024));
+        assert_eq!(upcoming[1].0, date(31, 3, 2024));
+        assert_eq!(upcoming[2].0, date(30, 4, 2024));
+    }
+}